@@ -0,0 +1,43 @@
+use ndarray::Array2;
+use num_dual::DualNumFloat;
+use crate::SolverReport;
+
+/// The roots of a report as an array with one row per root and the columns `root, residual`, in
+/// the order of `report.roots`, for analysis code built on `ndarray`.
+pub fn roots_to_array<T>(report: &SolverReport<T>) -> Array2<T> where T: DualNumFloat {
+    Array2::from_shape_fn((report.roots.len(), 2), |(i, j)| if j == 0 { report.roots[i].root } else { report.roots[i].residual })
+}
+
+/// The `(root, residual)` pairs of an array in the form returned by `roots_to_array`.
+///
+/// Panics unless the array has exactly two columns.
+pub fn roots_from_array<T>(array: &Array2<T>) -> Vec<(T, T)> where T: DualNumFloat {
+    if array.ncols() != 2 {
+        panic!("An array of roots must have the two columns root and residual, not {}", array.ncols())
+    }
+    array.rows().into_iter().map(|row| (row[0], row[1])).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{root_search_report, RootSearchOptions};
+    use num_dual::{Dual64, DualNum};
+
+    #[test]
+    fn round_trip_sine_roots_through_array() {
+        fn sine<D: DualNum<f64>>(x: D) -> D {
+            x.sin()
+        }
+        let report = root_search_report::<_,Dual64,f64>(sine, RootSearchOptions{
+            lower: -5.0,
+            upper: 5.0,
+            ..Default::default()
+        });
+        let array = roots_to_array(&report);
+        assert_eq!(array.dim(), (3, 2));
+        let pairs: Vec<(f64, f64)> = report.roots.iter().map(|root| (root.root, root.residual)).collect();
+        assert_eq!(roots_from_array(&array), pairs);
+        assert_eq!(array.column(0).to_vec(), report.roots.iter().map(|root| root.root).collect::<Vec<_>>());
+    }
+}
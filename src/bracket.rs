@@ -0,0 +1,269 @@
+use std::{fmt::Display, ops::{Div, Sub}};
+use num_dual::DualNumFloat;
+use crate::{
+    add_boundary_roots, even_guesses, find_bisections, from_scalar, solve_brackets, validated, BisectionOptions, Coerceable, Crossing,
+    Derivable, Interval, RootSearchOptions, RootSearchResult
+};
+
+/// A method of finding the intervals across which a function crosses the target.
+///
+/// This is the bracketing counterpart of [`Solver`](crate::Solver), so that `root_search_with`
+/// can pair any way of finding candidate intervals with the Newton solve of each of them.
+///
+/// A custom bracketer samples `f` in whatever way suits the problem and returns every interval
+/// `[a, b]` within `[lower, upper]` across which `f(x) - target` changes sign, in ascending
+/// order. Each interval should hold a single root strictly inside of it, as roots that Newton's
+/// method finds on or beyond the edges of their interval are discarded.
+pub trait Bracketer<T> where T: DualNumFloat {
+    fn brackets<F, N>(&self, f: F, lower: T, upper: T, target: T) -> Vec<Interval<T>>
+    where
+        F: Fn(N) -> N + Sync + Send,
+        N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div;
+}
+
+/// Evenly spaced samples as in `find_bisections`
+#[derive(Debug, Clone, Copy)]
+pub struct UniformGrid {
+    pub resolution: i32,
+    /// See `BisectionOptions::subdivisions`
+    pub subdivisions: i32
+}
+
+impl <T: DualNumFloat> Bracketer<T> for UniformGrid {
+    fn brackets<F, N>(&self, f: F, lower: T, upper: T, target: T) -> Vec<Interval<T>>
+    where
+        F: Fn(N) -> N + Sync + Send,
+        N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div
+    {
+        find_bisections(f, BisectionOptions{
+            lower,
+            upper,
+            resolution: self.resolution,
+            target,
+            subdivisions: self.subdivisions,
+            sign_epsilon: T::zero()
+        })
+    }
+}
+
+/// Samples spaced evenly in `ln(x)`, for roots spread over several orders of magnitude.
+///
+/// Both of the bounds must be positive.
+#[derive(Debug, Clone, Copy)]
+pub struct LogGrid {
+    pub resolution: i32
+}
+
+impl <T: DualNumFloat> Bracketer<T> for LogGrid {
+    fn brackets<F, N>(&self, f: F, lower: T, upper: T, target: T) -> Vec<Interval<T>>
+    where
+        F: Fn(N) -> N + Sync + Send,
+        N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div
+    {
+        if lower <= T::zero() {
+            panic!("Bounds of a logarithmic grid must be positive")
+        }
+        let ratio = (upper / lower).ln() / T::from(self.resolution).unwrap();
+        let samples: Vec<(T, T)> = (0..=self.resolution)
+            .map(|i| if i == self.resolution { upper } else { lower * (ratio * T::from(i).unwrap()).exp() })
+            .filter_map(|x| sample(&f, x, target).map(|fx| (x, fx)))
+            .collect();
+        sign_changes(&samples).into_iter().map(|(bracket, _)| bracket).collect()
+    }
+}
+
+/// Samples that start from an evenly spaced grid of `initial` intervals, each of which is halved
+/// up to `depth` times wherever the midpoint strays further than `tolerance` from the straight
+/// line between the endpoints, so that the sampling is densest where the function curves most.
+#[derive(Debug, Clone, Copy)]
+pub struct Adaptive<T> where T: DualNumFloat {
+    pub initial: i32,
+    pub depth: i32,
+    pub tolerance: T
+}
+
+impl <T: DualNumFloat> Adaptive<T> {
+    fn refine<F, N>(&self, f: &F, a: (T, T), b: (T, T), depth: i32, target: T, samples: &mut Vec<(T, T)>)
+    where
+        F: Fn(N) -> N,
+        N: Derivable<T> + Coerceable<T>
+    {
+        if depth == 0 || (a.1 > T::zero()) != (b.1 > T::zero()) {
+            return;
+        }
+        let mid = (a.0 + b.0) / T::from(2).unwrap();
+        let fm = match sample(f, mid, target) {
+            Some(fm) => fm,
+            None => return
+        };
+        if (fm - (a.1 + b.1) / T::from(2).unwrap()).abs() <= self.tolerance {
+            return;
+        }
+        self.refine(f, a, (mid, fm), depth - 1, target, samples);
+        samples.push((mid, fm));
+        self.refine(f, (mid, fm), b, depth - 1, target, samples);
+    }
+}
+
+impl <T: DualNumFloat> Bracketer<T> for Adaptive<T> {
+    fn brackets<F, N>(&self, f: F, lower: T, upper: T, target: T) -> Vec<Interval<T>>
+    where
+        F: Fn(N) -> N + Sync + Send,
+        N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div
+    {
+        let step = (upper - lower) / T::from(self.initial).unwrap();
+        let grid: Vec<(T, T)> = (0..=self.initial)
+            .map(|i| if i == self.initial { upper } else { lower + step * T::from(i).unwrap() })
+            .filter_map(|x| sample(&f, x, target).map(|fx| (x, fx)))
+            .collect();
+        let mut samples = Vec::new();
+        for pair in grid.windows(2) {
+            samples.push(pair[0]);
+            self.refine(&f, pair[0], pair[1], self.depth, target, &mut samples);
+        }
+        samples.extend(grid.last());
+        sign_changes(&samples).into_iter().map(|(bracket, _)| bracket).collect()
+    }
+}
+
+fn sample<F, N, T>(f: &F, x: T, target: T) -> Option<T>
+where
+    F: Fn(N) -> N,
+    N: Derivable<T> + Coerceable<T>,
+    T: DualNumFloat
+{
+    from_scalar(x).ok().map(|x| f(x).zeroth_derivative() - target)
+}
+
+/// Brackets every sign change between consecutive samples of `f - target`, bracketing a sample
+/// that is exactly zero by its neighbours so that it lies strictly inside of the bracket.
+pub(crate) fn sign_changes<T>(samples: &[(T, T)]) -> Vec<(Interval<T>, Crossing)> where T: DualNumFloat {
+    sign_changes_with(samples, |_| Vec::new())
+}
+
+/// Brackets the samples as `sign_changes` does, calling `between(i)` for every pair of samples `i`
+/// and `i + 1` that are non-zero and of the same sign, to bracket any roots the samples step over.
+///
+/// These are the rules of every grid of the crate, so that they bracket a root alike.
+pub(crate) fn sign_changes_with<T, B>(samples: &[(T, T)], mut between: B) -> Vec<(Interval<T>, Crossing)>
+where
+    T: DualNumFloat,
+    B: FnMut(usize) -> Vec<(Interval<T>, Crossing)>
+{
+    let mut brackets = Vec::new();
+    for (i, pair) in samples.windows(2).enumerate() {
+        let ((a, fa), (b, fb)) = (pair[0], pair[1]);
+        let crossing = if fa < fb { Crossing::Rising } else { Crossing::Falling };
+        // A sample that is exactly zero is a root on the grid itself. It is bracketed once, by the
+        // two intervals either side of it, so that the root lies strictly inside of the bracket.
+        // Only the first sample can be the left endpoint of such an interval without having been
+        // the right endpoint of the previous one.
+        if i == 0 && fa == T::zero() {
+            brackets.push((Interval::new(a, b), crossing));
+        }
+        if fb == T::zero() {
+            // The samples either side of the root tell a crossing from a touch, unless the root
+            // ends the samples
+            let bracket = match samples.get(i + 2) {
+                Some(&(c, fc)) if (fa > T::zero() && fc > T::zero()) || (fa < T::zero() && fc < T::zero()) => (Interval::new(a, c), Crossing::Touching),
+                Some(&(c, fc)) => (Interval::new(a, c), if fa < fc { Crossing::Rising } else { Crossing::Falling }),
+                None => (Interval::new(a, b), crossing)
+            };
+            brackets.push(bracket);
+        } else if (fa > T::zero() && fb < T::zero()) || (fa < T::zero() && fb > T::zero()) {
+            brackets.push((Interval::new(a, b), crossing));
+        } else if fa != T::zero() {
+            brackets.extend(between(i));
+        }
+    }
+    brackets
+}
+
+/// Searches for roots as in `root_search`, finding the brackets to solve with `bracketer` instead
+/// of the grid described by `opts`.
+///
+/// The resolution, subdivisions and symmetry hint of `opts` are ignored.
+pub fn root_search_with<F, B, N, T>(f: F, bracketer: &B, opts: RootSearchOptions<T>) -> RootSearchResult<T>
+where
+    F: Fn(N) -> N + Sync + Send,
+    B: Bracketer<T>,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    let opts = validated(opts);
+    let brackets = bracketer.brackets(&f, opts.lower, opts.upper, opts.target).into_iter()
+        .map(|bracket| {
+            let rising = match (sample(&f, bracket.lower, opts.target), sample(&f, bracket.upper, opts.target)) {
+                (Some(fa), Some(fb)) => fa < fb,
+                _ => true
+            };
+            (bracket, if rising { Crossing::Rising } else { Crossing::Falling })
+        })
+        .collect();
+    let mut result = solve_brackets(&f, &|_| true, &even_guesses, brackets, &opts);
+    add_boundary_roots(&f, &|_| true, &mut result, &opts);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::root_search;
+    use num_dual::{Dual32, DualNum};
+
+    #[test]
+    fn uniform_grid_matches_root_search() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let opts = RootSearchOptions{
+            lower: -5.0,
+            upper: 5.0,
+            ..Default::default()
+        };
+        let grid = UniformGrid{resolution: 1000, subdivisions: 0};
+        let res = root_search_with::<_,_,Dual32,f32>(&sine, &grid, opts);
+        let expected = root_search::<_,Dual32,f32>(&sine, opts);
+        assert_eq!(res.roots, expected.roots);
+        assert_eq!(res.crossings, expected.crossings);
+    }
+
+    #[test]
+    fn find_roots_across_magnitudes_with_log_grid() {
+        fn log_sine<D: DualNum<f32>>(x: D) -> D {
+            x.ln().sin()
+        }
+        let opts = RootSearchOptions{
+            lower: 0.001,
+            upper: 1000.0,
+            tolerance: 0.001,
+            ..Default::default()
+        };
+        let res = root_search_with::<_,_,Dual32,f32>(&log_sine, &LogGrid{resolution: 100}, opts);
+        assert_eq!(res.roots.len(), 5);
+        for (root, k) in res.roots.iter().zip(-2..=2) {
+            let expected = (k as f32 * std::f32::consts::PI).exp();
+            assert!((root - expected).abs() < 0.001 * expected.max(1.0));
+        }
+        let uniform = root_search_with::<_,_,Dual32,f32>(&log_sine, &UniformGrid{resolution: 100, subdivisions: 0}, opts);
+        assert!(uniform.roots.len() < 5);
+    }
+
+    #[test]
+    fn find_close_root_pair_with_adaptive_sampling() {
+        fn close_pair<D: DualNum<f32>>(x: D) -> D {
+            (x.clone() - 0.521) * (x - 0.531)
+        }
+        let opts = RootSearchOptions{
+            lower: 0.0,
+            upper: 1.0,
+            tolerance: 0.00001,
+            ..Default::default()
+        };
+        let adaptive = Adaptive{initial: 10, depth: 10, tolerance: 0.0001};
+        let res = root_search_with::<_,_,Dual32,f32>(&close_pair, &adaptive, opts);
+        assert_eq!(res.roots.len(), 2);
+        assert!((res.roots[0] - 0.521).abs() < 0.0001);
+        assert!((res.roots[1] - 0.531).abs() < 0.0001);
+    }
+}
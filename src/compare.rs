@@ -0,0 +1,393 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use num_dual::{Dual2, DualNum, DualNumFloat};
+use crate::{extrema::Value, HybridOptions, Interval, Newton, NewtonResult, Solver, TerminationReason};
+
+/// A root finding method run by `solver_comparison`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// Newton's method from the midpoint of the bracket
+    Newton,
+    /// The secant method from the two ends of the bracket
+    Secant,
+    /// Repeated halving of the bracket
+    Bisection,
+    /// Halley's method from the midpoint of the bracket, using the second derivative
+    Halley,
+    /// Brent's method, combining bisection with secant and inverse quadratic interpolation steps
+    Brent
+}
+
+/// How one method fared on the problem given to `solver_comparison`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison<T> where T: DualNumFloat {
+    pub method: Method,
+    pub root: Option<T>,
+    pub iterations: i32,
+    /// The number of times `f` was evaluated
+    pub evaluations: usize,
+    /// Whether the method met the tolerance
+    pub converged: bool
+}
+
+/// A method that solves a single bracket of `f` and reports how the solve went, the instrumented
+/// counterpart of `Solver` that `solver_comparison` runs.
+///
+/// `f` is evaluated in second order dual numbers as in the `extrema` module, as Halley's method
+/// needs the second derivative, and is solved for a root of `f` itself.
+pub trait BracketSolver<T> where T: DualNumFloat + DualNum<T> {
+    fn method(&self) -> Method;
+    fn solve_bracket(&self, f: &(dyn Fn(Dual2<T, T>) -> Dual2<T, T> + Sync), lower: T, upper: T) -> NewtonResult<T>;
+}
+
+/// Runs every `Method` on the bracket of `opts` and tabulates how each of them did, to help choose
+/// a solver for a family of similar problems.
+///
+/// The bracketing methods require `f - target` to change sign across the bracket, while the open
+/// methods only start from it and may converge to a root outside of it.
+pub fn solver_comparison<F, T>(f: F, opts: HybridOptions<T>) -> Vec<Comparison<T>>
+where
+    F: Fn(Dual2<T, T>) -> Dual2<T, T> + Sync + Send,
+    T: DualNumFloat + DualNum<T>
+{
+    let (patience, tolerance) = (opts.patience, opts.tolerance);
+    let solvers: [&dyn BracketSolver<T>; 5] = [
+        &Newton::new().with_patience(patience).with_tolerance(tolerance),
+        &Secant{patience, tolerance},
+        &Bisection{patience, tolerance},
+        &Halley{patience, tolerance},
+        &Brent{patience, tolerance}
+    ];
+    solvers.into_iter().map(|solver| {
+        let evaluations = AtomicUsize::new(0);
+        let counted = |x: Dual2<T, T>| {
+            evaluations.fetch_add(1, Ordering::Relaxed);
+            f(x) - opts.target
+        };
+        let res = solver.solve_bracket(&counted, opts.lower, opts.upper);
+        Comparison{
+            method: solver.method(),
+            root: res.root,
+            iterations: res.iterations,
+            evaluations: evaluations.into_inner(),
+            converged: res.termination == TerminationReason::ToleranceMet
+        }
+    }).collect()
+}
+
+/// Newton's method from the midpoint of the bracket
+impl <T: DualNumFloat + DualNum<T>> BracketSolver<T> for Newton<T> {
+    fn method(&self) -> Method {
+        Method::Newton
+    }
+
+    fn solve_bracket(&self, f: &(dyn Fn(Dual2<T, T>) -> Dual2<T, T> + Sync), lower: T, upper: T) -> NewtonResult<T> {
+        self.solve(|x: Value<T>| Value(f(x.0)), Interval::new(lower, upper).midpoint())
+    }
+}
+
+fn finished<T: DualNumFloat>(root: Option<T>, iterations: i32, termination: TerminationReason) -> NewtonResult<T> {
+    NewtonResult{root, iterations, termination}
+}
+
+/// The value of `f` along with its first and second derivative at `x`
+fn derivatives<T>(f: &(dyn Fn(Dual2<T, T>) -> Dual2<T, T> + Sync), x: T) -> (T, T, T) where T: DualNumFloat + DualNum<T> {
+    let z = f(Dual2::from_re(x).derivative());
+    (z.re, z.v1, z.v2)
+}
+
+/// Keeps the root of a solve over the range as the only root found by a bracket method used as a `Solver`
+fn roots_in<T: DualNumFloat>(res: NewtonResult<T>, lower: T, upper: T) -> Vec<T> {
+    res.root.filter(|root| lower <= *root && *root <= upper).into_iter().collect()
+}
+
+/// The secant method from the two ends of the bracket
+#[derive(Debug, Clone, Copy)]
+pub struct Secant<T> where T: DualNumFloat {
+    pub patience: i32,
+    pub tolerance: T
+}
+
+impl <T: DualNumFloat> Secant<T> {
+    /// Solves for a root of the function whose value and first two derivatives `eval` returns
+    pub fn solve<E>(&self, eval: E, lower: T, upper: T) -> NewtonResult<T> where E: Fn(T) -> (T, T, T) {
+        let (mut x0, mut x1) = (lower, upper);
+        let (mut f0, mut f1) = (eval(x0).0, eval(x1).0);
+        for count in 1..=self.patience {
+            if f1 == f0 {
+                return finished(None, count, TerminationReason::ZeroDerivative);
+            }
+            let x2 = x1 - f1 * (x1 - x0) / (f1 - f0);
+            if !x2.is_finite() {
+                return finished(None, count, TerminationReason::Diverged);
+            }
+            if (x2 - x1).abs() < self.tolerance {
+                return finished(Some(x2), count, TerminationReason::ToleranceMet);
+            }
+            (x0, f0) = (x1, f1);
+            x1 = x2;
+            f1 = eval(x1).0;
+        }
+        finished(None, self.patience, TerminationReason::PatienceExhausted)
+    }
+}
+
+impl <T: DualNumFloat + DualNum<T>> BracketSolver<T> for Secant<T> {
+    fn method(&self) -> Method {
+        Method::Secant
+    }
+
+    fn solve_bracket(&self, f: &(dyn Fn(Dual2<T, T>) -> Dual2<T, T> + Sync), lower: T, upper: T) -> NewtonResult<T> {
+        self.solve(|x| derivatives(f, x), lower, upper)
+    }
+}
+
+impl <F, T> Solver<F, Dual2<T, T>, T> for Secant<T>
+where
+    F: Fn(Dual2<T, T>) -> Dual2<T, T> + Sync + Send,
+    T: DualNumFloat + DualNum<T>
+{
+    fn find_roots(&self, f: F, lower: T, upper: T) -> Vec<T> {
+        roots_in(self.solve_bracket(&f, lower, upper), lower, upper)
+    }
+}
+
+/// Repeated halving of the bracket
+#[derive(Debug, Clone, Copy)]
+pub struct Bisection<T> where T: DualNumFloat {
+    pub patience: i32,
+    pub tolerance: T
+}
+
+impl <T: DualNumFloat> Bisection<T> {
+    /// Solves for a root of the function whose value and first two derivatives `eval` returns
+    pub fn solve<E>(&self, eval: E, lower: T, upper: T) -> NewtonResult<T> where E: Fn(T) -> (T, T, T) {
+        let two = T::from(2).unwrap();
+        let (mut lower, mut upper) = (lower, upper);
+        let (f_lower, f_upper) = (eval(lower).0, eval(upper).0);
+        if f_lower == T::zero() || f_upper == T::zero() {
+            return finished(Some(if f_lower == T::zero() { lower } else { upper }), 0, TerminationReason::ToleranceMet);
+        }
+        if (f_lower > T::zero()) == (f_upper > T::zero()) {
+            return finished(None, 0, TerminationReason::NotBracketed);
+        }
+        for count in 1..=self.patience {
+            let mid = (lower + upper) / two;
+            if (upper - lower) / two < self.tolerance {
+                return finished(Some(mid), count, TerminationReason::ToleranceMet);
+            }
+            let f_mid = eval(mid).0;
+            if f_mid == T::zero() {
+                return finished(Some(mid), count, TerminationReason::ToleranceMet);
+            }
+            if (f_mid > T::zero()) == (f_lower > T::zero()) {
+                lower = mid;
+            } else {
+                upper = mid;
+            }
+        }
+        finished(None, self.patience, TerminationReason::PatienceExhausted)
+    }
+}
+
+impl <T: DualNumFloat + DualNum<T>> BracketSolver<T> for Bisection<T> {
+    fn method(&self) -> Method {
+        Method::Bisection
+    }
+
+    fn solve_bracket(&self, f: &(dyn Fn(Dual2<T, T>) -> Dual2<T, T> + Sync), lower: T, upper: T) -> NewtonResult<T> {
+        self.solve(|x| derivatives(f, x), lower, upper)
+    }
+}
+
+impl <F, T> Solver<F, Dual2<T, T>, T> for Bisection<T>
+where
+    F: Fn(Dual2<T, T>) -> Dual2<T, T> + Sync + Send,
+    T: DualNumFloat + DualNum<T>
+{
+    fn find_roots(&self, f: F, lower: T, upper: T) -> Vec<T> {
+        roots_in(self.solve_bracket(&f, lower, upper), lower, upper)
+    }
+}
+
+/// Halley's method from the midpoint of the bracket, using the second derivative
+#[derive(Debug, Clone, Copy)]
+pub struct Halley<T> where T: DualNumFloat {
+    pub patience: i32,
+    pub tolerance: T
+}
+
+impl <T: DualNumFloat> Halley<T> {
+    /// Solves for a root of the function whose value and first two derivatives `eval` returns
+    pub fn solve<E>(&self, eval: E, lower: T, upper: T) -> NewtonResult<T> where E: Fn(T) -> (T, T, T) {
+        let two = T::from(2).unwrap();
+        let mut current = Interval::new(lower, upper).midpoint();
+        for count in 1..=self.patience {
+            let (value, slope, curvature) = eval(current);
+            let denominator = two * slope * slope - value * curvature;
+            if denominator == T::zero() {
+                return finished(None, count, TerminationReason::ZeroDerivative);
+            }
+            let next = current - two * value * slope / denominator;
+            if !next.is_finite() {
+                return finished(None, count, TerminationReason::Diverged);
+            }
+            if (next - current).abs() < self.tolerance {
+                return finished(Some(next), count, TerminationReason::ToleranceMet);
+            }
+            current = next;
+        }
+        finished(None, self.patience, TerminationReason::PatienceExhausted)
+    }
+}
+
+impl <T: DualNumFloat + DualNum<T>> BracketSolver<T> for Halley<T> {
+    fn method(&self) -> Method {
+        Method::Halley
+    }
+
+    fn solve_bracket(&self, f: &(dyn Fn(Dual2<T, T>) -> Dual2<T, T> + Sync), lower: T, upper: T) -> NewtonResult<T> {
+        self.solve(|x| derivatives(f, x), lower, upper)
+    }
+}
+
+impl <F, T> Solver<F, Dual2<T, T>, T> for Halley<T>
+where
+    F: Fn(Dual2<T, T>) -> Dual2<T, T> + Sync + Send,
+    T: DualNumFloat + DualNum<T>
+{
+    fn find_roots(&self, f: F, lower: T, upper: T) -> Vec<T> {
+        roots_in(self.solve_bracket(&f, lower, upper), lower, upper)
+    }
+}
+
+/// Brent's method, combining bisection with secant and inverse quadratic interpolation steps
+#[derive(Debug, Clone, Copy)]
+pub struct Brent<T> where T: DualNumFloat {
+    pub patience: i32,
+    pub tolerance: T
+}
+
+impl <T: DualNumFloat> Brent<T> {
+    /// Solves for a root as in `zbrent` of Numerical Recipes, where `b` is the best estimate of the
+    /// root, `a` the previous one and `c` the other end of the bracket around `b`
+    pub fn solve<E>(&self, eval: E, lower: T, upper: T) -> NewtonResult<T> where E: Fn(T) -> (T, T, T) {
+        let (two, three, half) = (T::from(2).unwrap(), T::from(3).unwrap(), T::from(0.5).unwrap());
+        let (mut a, mut b) = (lower, upper);
+        let (mut fa, mut fb) = (eval(a).0, eval(b).0);
+        if fa == T::zero() || fb == T::zero() {
+            return finished(Some(if fa == T::zero() { a } else { b }), 0, TerminationReason::ToleranceMet);
+        }
+        if (fa > T::zero()) == (fb > T::zero()) {
+            return finished(None, 0, TerminationReason::NotBracketed);
+        }
+        let (mut c, mut fc) = (b, fb);
+        let (mut d, mut e) = (b - a, b - a);
+        for count in 1..=self.patience {
+            if (fb > T::zero()) == (fc > T::zero()) {
+                (c, fc) = (a, fa);
+                d = b - a;
+                e = d;
+            }
+            if fc.abs() < fb.abs() {
+                (a, fa) = (b, fb);
+                (b, fb) = (c, fc);
+                (c, fc) = (a, fa);
+            }
+            let tolerance = two * T::epsilon() * b.abs() + half * self.tolerance;
+            let xm = half * (c - b);
+            if xm.abs() <= tolerance || fb == T::zero() {
+                return finished(Some(b), count, TerminationReason::ToleranceMet);
+            }
+            if e.abs() >= tolerance && fa.abs() > fb.abs() {
+                // Interpolate, with a secant step when only two distinct points are known
+                let s = fb / fa;
+                let (mut p, mut q) = if a == c {
+                    (two * xm * s, T::one() - s)
+                } else {
+                    let (q, r) = (fa / fc, fb / fc);
+                    (s * (two * xm * q * (q - r) - (b - a) * (r - T::one())), (q - T::one()) * (r - T::one()) * (s - T::one()))
+                };
+                if p > T::zero() {
+                    q = -q;
+                }
+                p = p.abs();
+                // The interpolation is only taken while it stays within the bracket and converges
+                // faster than bisection would
+                if two * p < (three * xm * q - (tolerance * q).abs()).min((e * q).abs()) {
+                    e = d;
+                    d = p / q;
+                } else {
+                    d = xm;
+                    e = d;
+                }
+            } else {
+                d = xm;
+                e = d;
+            }
+            (a, fa) = (b, fb);
+            b = b + if d.abs() > tolerance { d } else { tolerance.copysign(xm) };
+            fb = eval(b).0;
+        }
+        finished(None, self.patience, TerminationReason::PatienceExhausted)
+    }
+}
+
+impl <T: DualNumFloat + DualNum<T>> BracketSolver<T> for Brent<T> {
+    fn method(&self) -> Method {
+        Method::Brent
+    }
+
+    fn solve_bracket(&self, f: &(dyn Fn(Dual2<T, T>) -> Dual2<T, T> + Sync), lower: T, upper: T) -> NewtonResult<T> {
+        self.solve(|x| derivatives(f, x), lower, upper)
+    }
+}
+
+impl <F, T> Solver<F, Dual2<T, T>, T> for Brent<T>
+where
+    F: Fn(Dual2<T, T>) -> Dual2<T, T> + Sync + Send,
+    T: DualNumFloat + DualNum<T>
+{
+    fn find_roots(&self, f: F, lower: T, upper: T) -> Vec<T> {
+        roots_in(self.solve_bracket(&f, lower, upper), lower, upper)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compare_solvers_on_sine_root() {
+        use std::f64::consts::PI;
+        let table = solver_comparison(|x: Dual2<f64, f64>| x.sin(), HybridOptions{
+            lower: 2.0,
+            upper: 4.5,
+            patience: 1000,
+            tolerance: 1e-10,
+            target: 0.0
+        });
+        let methods: Vec<Method> = table.iter().map(|row| row.method).collect();
+        assert_eq!(methods, vec![Method::Newton, Method::Secant, Method::Bisection, Method::Halley, Method::Brent]);
+        for row in &table {
+            assert!(row.converged, "{:?} did not converge", row.method);
+            assert!((row.root.unwrap() - PI).abs() < 1e-9, "{:?} found {:?}", row.method, row.root);
+            assert!(row.evaluations > 0);
+        }
+        let bisection = table[2];
+        assert!(table.iter().all(|row| row.iterations <= bisection.iterations));
+        let unbracketed = solver_comparison(|x: Dual2<f64, f64>| x.sin(), HybridOptions{
+            lower: 0.5,
+            upper: 2.5,
+            patience: 1000,
+            tolerance: 1e-10,
+            target: 0.0
+        });
+        assert!(!unbracketed[2].converged && !unbracketed[4].converged);
+        // Each method is also a `Solver` over a range
+        let brent = Brent{patience: 1000, tolerance: 1e-10};
+        let roots = Solver::<_,Dual2<f64, f64>,f64>::find_roots(&brent, |x: Dual2<f64, f64>| x.sin(), 2.0, 4.5);
+        assert_eq!(roots.len(), 1);
+        assert!((roots[0] - PI).abs() < 1e-9);
+        assert!(Solver::<_,Dual2<f64, f64>,f64>::find_roots(&brent, |x: Dual2<f64, f64>| x.sin(), 0.5, 2.5).is_empty());
+    }
+}
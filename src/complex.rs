@@ -0,0 +1,71 @@
+use nalgebra::Complex;
+use num_dual::DualNumFloat;
+use crate::{CoerceError, Coerceable, Derivable};
+
+/// A complex number used for complex-step differentiation, as an alternative to dual numbers.
+///
+/// For a real analytic `f`, `f(x + ih) = f(x) + ih f'(x) + O(h^2)`, so the imaginary part divided
+/// by a tiny `h` is the first derivative to within rounding, without the cancellation of a finite
+/// difference. Functions are written over `Complex<T>` and may use any of its analytic functions,
+/// but not `abs`, comparisons or anything else that is not complex analytic.
+///
+/// Only the first derivative is available, so this cannot stand in for the second order dual
+/// numbers of Halley's method or the `extrema` module.
+pub type ComplexStep<T> = Complex<T>;
+
+/// The imaginary step `h`, far below the precision of the real part so that it never perturbs it
+const COMPLEX_STEP: f64 = 1e-20;
+
+impl <T: DualNumFloat> Derivable<T> for Complex<T> {
+    fn execute_derivative(&self) -> Self {
+        Complex::new(self.re, T::from(COMPLEX_STEP).unwrap())
+    }
+    fn zeroth_derivative(&self) -> T {
+        self.re
+    }
+    fn first_derivative(&self) -> T {
+        self.im / T::from(COMPLEX_STEP).unwrap()
+    }
+}
+
+impl <T: DualNumFloat> Coerceable<T> for Complex<T> {
+    fn coerce_to(&self) -> Result<T, CoerceError> {
+        Ok(self.re)
+    }
+    fn coerce_from(value: T) -> Result<Self, CoerceError> {
+        Ok(Complex::new(value, T::zero()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{newton, root_search, NewtonOptions, RootSearchOptions};
+    use nalgebra::ComplexField;
+    use num_dual::{Dual64, DualNum};
+
+    #[test]
+    fn complex_step_newton_matches_dual_newton() {
+        fn sine<D: DualNum<f64>>(x: D) -> D {
+            x.sin()
+        }
+        let complex_sine = |x: ComplexStep<f64>| x.sin();
+        let x = Complex::new(1.0, 0.0).execute_derivative();
+        assert_eq!(complex_sine(x).first_derivative(), 1.0_f64.cos());
+        let opts = || NewtonOptions{
+            guess: 2.0,
+            tolerance: 1e-12,
+            ..Default::default()
+        };
+        let complex = newton::<_,ComplexStep<f64>,f64>(complex_sine, opts());
+        let dual = newton::<_,Dual64,f64>(sine, opts());
+        assert_eq!(complex.root, dual.root);
+        assert_eq!(complex.iterations, dual.iterations);
+        let res = root_search::<_,ComplexStep<f64>,f64>(complex_sine, RootSearchOptions{
+            lower: -5.0,
+            upper: 5.0,
+            ..Default::default()
+        });
+        assert_eq!(res.roots.len(), 3);
+    }
+}
@@ -0,0 +1,43 @@
+use std::io::{self, Write};
+use num_dual::DualNumFloat;
+use crate::VerboseNewtonResult;
+
+/// Writes the history of a verbose Newton solve to `writer` as CSV, with a header row followed by
+/// one `iteration,x,residual` row per iterate, counting the guess as iteration zero.
+pub fn write_residuals<W, T>(writer: &mut W, trace: &VerboseNewtonResult<T>) -> io::Result<()>
+where
+    W: Write,
+    T: DualNumFloat
+{
+    writeln!(writer, "iteration,x,residual")?;
+    for (iteration, (x, residual)) in trace.iterates.iter().zip(&trace.residuals).enumerate() {
+        writeln!(writer, "{},{},{}", iteration, x, residual)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{newton_verbose, NewtonOptions};
+    use num_dual::{Dual64, DualNum};
+
+    #[test]
+    fn write_sine_residuals() {
+        fn sine<D: DualNum<f64>>(x: D) -> D {
+            x.sin()
+        }
+        let trace = newton_verbose::<_,Dual64,f64>(sine, NewtonOptions{
+            guess: 3.0,
+            ..Default::default()
+        });
+        let mut buffer = Vec::new();
+        write_residuals(&mut buffer, &trace).unwrap();
+        let csv = String::from_utf8(buffer).unwrap();
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows.len(), trace.residuals.len() + 1);
+        assert_eq!(rows[0], "iteration,x,residual");
+        assert_eq!(rows[1], format!("0,3,{}", 3.0_f64.sin()));
+        assert_eq!(rows[2], format!("1,{},{}", trace.iterates[1], trace.iterates[1].sin()));
+    }
+}
@@ -0,0 +1,193 @@
+use std::{fmt, ops::{Add, Div, Mul, Neg, Sub}};
+use twofloat::TwoFloat;
+use crate::{CoerceError, Coerceable, Derivable};
+
+/// A dual number over the double-double precision `TwoFloat`.
+///
+/// `num_dual` only implements its dual numbers over `f32` and `f64`, and the orphan rule keeps
+/// `TwoFloat` from being added to them from outside of either crate, so this is a minimal dual
+/// number of its own. It supports the arithmetic operators, both between dual numbers and with
+/// `TwoFloat` or `f64` scalars, and the elementary functions below.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DualTwoFloat {
+    pub re: TwoFloat,
+    pub eps: TwoFloat
+}
+
+impl DualTwoFloat {
+    pub fn new(re: TwoFloat, eps: TwoFloat) -> Self {
+        DualTwoFloat{re, eps}
+    }
+
+    pub fn from_re(re: TwoFloat) -> Self {
+        DualTwoFloat{re, eps: TwoFloat::from(0.0)}
+    }
+
+    fn chain(self, re: TwoFloat, derivative: TwoFloat) -> Self {
+        DualTwoFloat{re, eps: self.eps * derivative}
+    }
+
+    pub fn powi(self, n: i32) -> Self {
+        match n {
+            0 => DualTwoFloat::from_re(TwoFloat::from(1.0)),
+            _ => self.chain(self.re.powi(n), TwoFloat::from(n) * self.re.powi(n - 1))
+        }
+    }
+
+    pub fn sqrt(self) -> Self {
+        let re = self.re.sqrt();
+        self.chain(re, TwoFloat::from(0.5) / re)
+    }
+
+    pub fn exp(self) -> Self {
+        let re = self.re.exp();
+        self.chain(re, re)
+    }
+
+    pub fn ln(self) -> Self {
+        self.chain(self.re.ln(), self.re.recip())
+    }
+
+    pub fn sin(self) -> Self {
+        self.chain(self.re.sin(), self.re.cos())
+    }
+
+    pub fn cos(self) -> Self {
+        self.chain(self.re.cos(), -self.re.sin())
+    }
+}
+
+impl fmt::Display for DualTwoFloat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} + {}ε", self.re, self.eps)
+    }
+}
+
+impl Neg for DualTwoFloat {
+    type Output = Self;
+    fn neg(self) -> Self {
+        DualTwoFloat{re: -self.re, eps: -self.eps}
+    }
+}
+
+impl Add for DualTwoFloat {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        DualTwoFloat{re: self.re + other.re, eps: self.eps + other.eps}
+    }
+}
+
+impl Sub for DualTwoFloat {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        DualTwoFloat{re: self.re - other.re, eps: self.eps - other.eps}
+    }
+}
+
+impl Mul for DualTwoFloat {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        DualTwoFloat{re: self.re * other.re, eps: self.eps * other.re + self.re * other.eps}
+    }
+}
+
+impl Div for DualTwoFloat {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        DualTwoFloat{
+            re: self.re / other.re,
+            eps: (self.eps * other.re - self.re * other.eps) / (other.re * other.re)
+        }
+    }
+}
+
+macro_rules! impl_scalar_ops {
+    ($scalar:ty) => {
+        impl Add<$scalar> for DualTwoFloat {
+            type Output = Self;
+            fn add(self, other: $scalar) -> Self {
+                self + DualTwoFloat::from_re(TwoFloat::from(other))
+            }
+        }
+
+        impl Sub<$scalar> for DualTwoFloat {
+            type Output = Self;
+            fn sub(self, other: $scalar) -> Self {
+                self - DualTwoFloat::from_re(TwoFloat::from(other))
+            }
+        }
+
+        impl Mul<$scalar> for DualTwoFloat {
+            type Output = Self;
+            fn mul(self, other: $scalar) -> Self {
+                self * DualTwoFloat::from_re(TwoFloat::from(other))
+            }
+        }
+
+        impl Div<$scalar> for DualTwoFloat {
+            type Output = Self;
+            fn div(self, other: $scalar) -> Self {
+                self / DualTwoFloat::from_re(TwoFloat::from(other))
+            }
+        }
+    };
+}
+
+impl_scalar_ops!(TwoFloat);
+impl_scalar_ops!(f64);
+
+impl Derivable<TwoFloat> for DualTwoFloat {
+    fn execute_derivative(&self) -> Self {
+        DualTwoFloat{re: self.re, eps: TwoFloat::from(1.0)}
+    }
+    fn zeroth_derivative(&self) -> TwoFloat {
+        self.re
+    }
+    fn first_derivative(&self) -> TwoFloat {
+        self.eps
+    }
+}
+
+/// Only implemented for `TwoFloat` itself, as converting through another float would lose precision
+impl Coerceable<TwoFloat> for DualTwoFloat {
+    fn coerce_to(&self) -> Result<TwoFloat, CoerceError> {
+        Ok(self.re)
+    }
+    fn coerce_from(value: TwoFloat) -> Result<Self, CoerceError> {
+        if value.is_valid() {
+            Ok(DualTwoFloat::from_re(value))
+        } else {
+            Err(CoerceError::NotRepresentable)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{newton, root_search, NewtonOptions, RootSearchOptions};
+
+    #[test]
+    fn find_root_beyond_double_precision() {
+        // The root lies closer to one than `f64` can resolve, so it rounds to exactly one in `f64`
+        let offset = TwoFloat::from(1e-20);
+        let root = TwoFloat::from(1.0) + offset;
+        assert_eq!(f64::from(root), 1.0);
+        let shifted = move |x: DualTwoFloat| (x - root) * (x + 2.0);
+        let res = newton::<_,DualTwoFloat,TwoFloat>(&shifted, NewtonOptions{
+            guess: TwoFloat::from(2.0),
+            tolerance: TwoFloat::from(1e-28),
+            ..Default::default()
+        });
+        let found = res.root.unwrap();
+        assert!(((found - 1.0) - offset).abs() < TwoFloat::from(1e-28));
+        let res = root_search::<_,DualTwoFloat,TwoFloat>(&shifted, RootSearchOptions{
+            lower: TwoFloat::from(0.0),
+            upper: TwoFloat::from(3.0),
+            tolerance: TwoFloat::from(1e-28),
+            ..Default::default()
+        });
+        assert_eq!(res.roots.len(), 1);
+        assert!(((res.roots[0] - 1.0) - offset).abs() < TwoFloat::from(1e-28));
+    }
+}
@@ -0,0 +1,306 @@
+use std::{fmt, ops::{Div, Sub}};
+use num_dual::{Dual2, DualNum, DualNumFloat};
+use crate::{
+    add_boundary_roots, bracket::sign_changes, even_guesses, find_brackets_in_domain, newton_bisection, root_search, solve_scan,
+    validated, CoerceError, Coerceable, Crossing, Derivable, GridScan, HybridOptions, InOrder, Interval, RootSearchOptions,
+    RootSearchResult
+};
+
+/// The first derivative of a function evaluated in second order dual numbers, as a `Derivable`
+/// whose value is `f'(x)` and whose derivative is `f''(x)`, so that the solvers find the roots of `f'`.
+///
+/// The derivative part is seeded on every conversion from a scalar, as the slope can only be read
+/// from a seeded evaluation.
+#[derive(Debug, Clone, Copy)]
+pub struct Slope<T>(pub Dual2<T, T>) where T: DualNum<T>;
+
+impl <T: DualNumFloat + DualNum<T>> Derivable<T> for Slope<T> {
+    fn execute_derivative(&self) -> Self {
+        Slope(self.0.derivative())
+    }
+    fn zeroth_derivative(&self) -> T {
+        self.0.v1
+    }
+    fn first_derivative(&self) -> T {
+        self.0.v2
+    }
+}
+
+impl <T: DualNumFloat + DualNum<T>> Coerceable<T> for Slope<T> {
+    fn coerce_to(&self) -> Result<T, CoerceError> {
+        Ok(self.0.re)
+    }
+    fn coerce_from(value: T) -> Result<Self, CoerceError> {
+        Ok(Slope(Dual2::from_re(value).derivative()))
+    }
+}
+
+impl <T: DualNumFloat + DualNum<T>> fmt::Display for Slope<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl <T: DualNumFloat + DualNum<T>> Sub for Slope<T> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Slope(self.0 - other.0)
+    }
+}
+
+impl <T: DualNumFloat + DualNum<T>> Div for Slope<T> {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        Slope(self.0 / other.0)
+    }
+}
+
+/// A function evaluated in second order dual numbers, as a `Derivable` whose value is `f(x)` and
+/// whose derivative is `f'(x)`, so that the same evaluations serve the roots of `f` and of `f'`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Value<T>(pub(crate) Dual2<T, T>) where T: DualNum<T>;
+
+impl <T: DualNumFloat + DualNum<T>> Derivable<T> for Value<T> {
+    fn execute_derivative(&self) -> Self {
+        Value(self.0.derivative())
+    }
+    fn zeroth_derivative(&self) -> T {
+        self.0.re
+    }
+    fn first_derivative(&self) -> T {
+        self.0.v1
+    }
+}
+
+impl <T: DualNumFloat + DualNum<T>> Coerceable<T> for Value<T> {
+    fn coerce_to(&self) -> Result<T, CoerceError> {
+        Ok(self.0.re)
+    }
+    fn coerce_from(value: T) -> Result<Self, CoerceError> {
+        Ok(Value(Dual2::from_re(value)))
+    }
+}
+
+impl <T: DualNumFloat + DualNum<T>> fmt::Display for Value<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Searches for the critical points of `f` within the bounds of `opts`, i.e. the roots of `f'`.
+///
+/// A `Rising` crossing of `f'` is a minimum of `f` and a `Falling` one is a maximum.
+pub fn critical_points<F, T>(f: F, opts: RootSearchOptions<T>) -> RootSearchResult<T>
+where
+    F: Fn(Dual2<T, T>) -> Dual2<T, T> + Sync + Send,
+    T: DualNumFloat + DualNum<T>
+{
+    root_search(|x: Slope<T>| Slope(f(x.0)), RootSearchOptions{target: T::zero(), ..opts})
+}
+
+/// Searches for both the roots of `f` and its critical points within the bounds of `opts`, as
+/// `root_search` and `critical_points` do, from a single pass over the grid.
+///
+/// Each sample of the grid yields `f` and `f'` together, so the brackets of both are found at
+/// once. The roots are bracketed exactly as `root_search` brackets them, while the critical points
+/// are bracketed by the sign changes of `f'` at the same samples, without subdividing the grid.
+/// The critical points are always the roots of `f'`, whatever the target of `opts`, and the
+/// symmetry hint of `opts` is ignored.
+pub fn roots_and_critical_points<F, T>(f: F, opts: RootSearchOptions<T>) -> (RootSearchResult<T>, RootSearchResult<T>)
+where
+    F: Fn(Dual2<T, T>) -> Dual2<T, T> + Sync + Send,
+    T: DualNumFloat + DualNum<T>
+{
+    let opts = validated(opts);
+    let value = |x: Value<T>| Value(f(x.0));
+    let slope = |x: Slope<T>| Slope(f(x.0));
+    let slope_opts = RootSearchOptions{target: T::zero(), ..opts};
+    let scan = find_brackets_in_domain(value, |_| true, opts.bisection_options());
+    let slopes: Vec<(T, T)> = scan.samples.iter().zip(&scan.slopes).map(|((x, _), slope)| (*x, *slope)).collect();
+    let slope_scan = GridScan{
+        brackets: sign_changes(&slopes),
+        range: slopes.iter().fold(None, |range, (_, slope)| Some(range.map_or((*slope, *slope), |(min, max): (T, T)| (min.min(*slope), max.max(*slope))))),
+        slopes: Vec::new(),
+        samples: slopes
+    };
+    // A flat function has no roots to report, as in `root_search`
+    let mut roots = solve_scan(value, &|_| true, &even_guesses, scan, &opts, &InOrder).unwrap_or_else(|_| RootSearchResult::empty());
+    add_boundary_roots(&value, &|_| true, &mut roots, &opts);
+    let mut critical = solve_scan(slope, &|_| true, &even_guesses, slope_scan, &slope_opts, &InOrder).unwrap_or_else(|_| RootSearchResult::empty());
+    add_boundary_roots(&slope, &|_| true, &mut critical, &slope_opts);
+    (roots, critical)
+}
+
+/// Searches for the roots of `f` within the bounds of `opts` by splitting the range at the critical
+/// points of `f` into segments on which it is monotone, then solving every segment whose ends
+/// straddle the target with `newton_bisection`, which cannot fail on a sign change.
+///
+/// A monotone segment holds at most one root, so roots closer together than the spacing of the
+/// grid are all found as long as the extrema between them are. A root at which `f` only touches
+/// the target has no segment straddling it and is not found. The critical points are searched for
+/// on the grid of `opts`, where `subdivisions` narrows in on pairs of them that fall between two
+/// samples, and the symmetry hint is ignored.
+pub fn solve_monotone_segments<F, T>(f: F, opts: RootSearchOptions<T>) -> RootSearchResult<T>
+where
+    F: Fn(Dual2<T, T>) -> Dual2<T, T> + Sync + Send,
+    T: DualNumFloat + DualNum<T>
+{
+    let opts = validated(opts);
+    let value = |x: Value<T>| Value(f(x.0));
+    let sample = |x: T| f(Dual2::from_re(x)).re - opts.target;
+    let mut edges = vec![opts.lower];
+    edges.extend(critical_points(&f, opts).roots.into_iter().filter(|x| opts.lower < *x && *x < opts.upper));
+    edges.push(opts.upper);
+    let samples: Vec<(T, T)> = edges.iter().map(|x| (*x, sample(*x))).collect();
+    let mut found: Vec<(T, Crossing, Interval<T>)> = Vec::new();
+    for (i, pair) in samples.windows(2).enumerate() {
+        let ((a, fa), (b, fb)) = (pair[0], pair[1]);
+        let crossing = if fa < fb { Crossing::Rising } else { Crossing::Falling };
+        if (fa < T::zero() && fb > T::zero()) || (fa > T::zero() && fb < T::zero()) {
+            let res = newton_bisection(value, HybridOptions{
+                lower: a,
+                upper: b,
+                patience: opts.patience,
+                tolerance: opts.tolerance,
+                target: opts.target
+            });
+            if let Some(root) = res.root {
+                found.push((root, crossing, Interval::new(a, b)));
+            }
+        } else if fb == T::zero() && i + 2 < samples.len() {
+            // A root on a critical point, e.g. an inflection, crosses between the segments either side of it
+            let (c, fc) = samples[i + 2];
+            if (fa < T::zero()) != (fc < T::zero()) && fa != T::zero() && fc != T::zero() {
+                found.push((b, crossing, Interval::new(a, c)));
+            }
+        }
+    }
+    found.retain(|(_, crossing, _)| opts.direction.is_none_or(|direction| direction == *crossing));
+    let mut result = RootSearchResult{
+        roots: found.iter().map(|(root, _, _)| *root).collect(),
+        crossings: found.iter().map(|(_, crossing, _)| *crossing).collect(),
+        bisections: found.iter().map(|(_, _, segment)| *segment).collect(),
+        ..RootSearchResult::empty()
+    };
+    add_boundary_roots(&value, &|_| true, &mut result, &opts);
+    result
+}
+
+/// Finds the minimum of `f` over `[lower, upper]`, returning where it lies and the value of `f` there.
+///
+/// The interior minima found by `critical_points` are compared against the values at the bounds,
+/// which replace the bounds of `opts`, so a minimum on the boundary is found as well.
+pub fn minimize_bounded<F, T>(f: F, lower: T, upper: T, opts: RootSearchOptions<T>) -> (T, T)
+where
+    F: Fn(Dual2<T, T>) -> Dual2<T, T> + Sync + Send,
+    T: DualNumFloat + DualNum<T>
+{
+    let critical = critical_points(&f, RootSearchOptions{lower, upper, ..opts});
+    let minima = critical.roots.into_iter().zip(critical.crossings)
+        .filter(|(_, crossing)| *crossing == Crossing::Rising)
+        .map(|(x, _)| x);
+    [lower, upper].into_iter().chain(minima)
+        .map(|x| (x, f(Dual2::from_re(x)).re))
+        .fold((lower, T::infinity()), |best, candidate| if candidate.1 < best.1 { candidate } else { best })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_dual::{Dual32, Dual64};
+
+    #[test]
+    fn find_sine_extrema() {
+        use std::f32::consts::FRAC_PI_2;
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let res = critical_points(sine, RootSearchOptions{
+            lower: -5.0,
+            upper: 5.0,
+            ..Default::default()
+        });
+        assert_eq!(res.roots.len(), 4);
+        for (root, k) in res.roots.iter().zip([-3.0, -1.0, 1.0, 3.0]) {
+            assert!((root - k * FRAC_PI_2).abs() < 0.0001);
+        }
+        assert_eq!(res.crossings, vec![Crossing::Falling, Crossing::Rising, Crossing::Falling, Crossing::Rising]);
+    }
+
+    #[test]
+    fn find_sine_roots_and_extrema_together() {
+        use std::f32::consts::FRAC_PI_2;
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let opts = RootSearchOptions{
+            lower: -5.0,
+            upper: 5.0,
+            ..Default::default()
+        };
+        let (roots, critical) = roots_and_critical_points(sine, opts);
+        assert_eq!(roots.roots, root_search::<_,Dual32,f32>(sine, opts).roots);
+        assert_eq!(roots.crossings, vec![Crossing::Falling, Crossing::Rising, Crossing::Falling]);
+        assert_eq!(critical.roots.len(), 4);
+        for (root, k) in critical.roots.iter().zip([-3.0, -1.0, 1.0, 3.0]) {
+            assert!((root - k * FRAC_PI_2).abs() < 0.0001);
+        }
+        assert_eq!(critical.crossings, critical_points(sine, opts).crossings);
+        // The roots either side of the vertex lie between two samples, so only subdividing the
+        // grid as `root_search` does can find them
+        fn dip<D: DualNum<f32>>(x: D) -> D {
+            (x - 1.0).powi(2) - 0.0001
+        }
+        let opts = RootSearchOptions{
+            lower: 0.1,
+            upper: 2.1,
+            resolution: 8,
+            subdivisions: 10,
+            ..Default::default()
+        };
+        let (roots, critical) = roots_and_critical_points(dip, opts);
+        assert_eq!(roots.roots.len(), 2);
+        assert_eq!(roots.roots, root_search::<_,Dual32,f32>(dip, opts).roots);
+        assert!((critical.roots[0] - 1.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn find_close_roots_on_monotone_segments() {
+        fn cubic<D: DualNum<f64>>(x: D) -> D {
+            (x.clone() - 1.0) * (x.clone() - 1.1) * (x - 1.2)
+        }
+        // A single interval holds all three roots, which no sign change on the grid can reveal
+        let opts = RootSearchOptions{
+            lower: 0.5,
+            upper: 1.5,
+            resolution: 1,
+            subdivisions: 10,
+            ..Default::default()
+        };
+        assert_eq!(root_search::<_,Dual64,f64>(cubic, opts).roots.len(), 1);
+        let res = solve_monotone_segments(cubic, opts);
+        assert_eq!(res.roots.len(), 3);
+        for (root, expected) in res.roots.iter().zip([1.0, 1.1, 1.2]) {
+            assert!((root - expected).abs() < 0.0001);
+        }
+        assert_eq!(res.crossings, vec![Crossing::Rising, Crossing::Falling, Crossing::Rising]);
+        fn cube<D: DualNum<f64>>(x: D) -> D {
+            x.powi(3)
+        }
+        let res = solve_monotone_segments(cube, RootSearchOptions{lower: -1.0, upper: 2.0, ..Default::default()});
+        assert_eq!(res.roots.len(), 1);
+        assert!(res.roots[0].abs() < 0.001);
+    }
+
+    #[test]
+    fn minimize_within_bounds() {
+        fn shifted_square<D: DualNum<f64>>(x: D) -> D {
+            (x - 3.0).powi(2)
+        }
+        let (x, fx) = minimize_bounded(shifted_square, 0.0, 1.0, RootSearchOptions::default());
+        assert_eq!((x, fx), (1.0, 4.0));
+        let (x, fx) = minimize_bounded(shifted_square, 0.0, 5.0, RootSearchOptions::default());
+        assert!((x - 3.0).abs() < 0.0001);
+        assert!(fx < 0.0001);
+    }
+}
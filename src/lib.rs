@@ -1,6 +1,22 @@
-use std::{env, fmt::Display, ops::{Sub, Div}};
+use std::{cell::{Cell, RefCell}, collections::BTreeMap, env, fmt::{self, Display}, hash::{Hash, Hasher}, marker::PhantomData, ops::{Sub, Div}, sync::{atomic::{AtomicUsize, Ordering}, Mutex}, time::{Duration, Instant}, vec::IntoIter};
 // use std::{sync::mpsc::{Sender, Receiver, channel}, thread::{Thread,spawn, JoinHandle}};
-use num_dual::{DualNumFloat,Dual32};
+use nalgebra::{Dyn, U1};
+use num_dual::{Derivative, DualNum, DualNumFloat, Dual32, Dual64, DualDVec32, DualVec};
+
+#[cfg(feature = "ndarray")]
+pub mod array;
+pub mod bracket;
+pub mod compare;
+pub mod complex;
+#[cfg(feature = "csv")]
+pub mod csv;
+#[cfg(feature = "twofloat")]
+pub mod extended;
+pub mod extrema;
+pub mod poly;
+pub mod system;
+#[cfg(feature = "std-threads")]
+pub mod threads;
 
 pub trait Derivable<T> where T: DualNumFloat {
     fn execute_derivative(&self) -> Self;
@@ -9,237 +25,4437 @@ pub trait Derivable<T> where T: DualNumFloat {
 }
 
 pub trait Coerceable<T> where T: DualNumFloat{
-    fn coerce_to(&self) -> T;
-    fn coerce_from(value: T) -> Self;
+    fn coerce_to(&self) -> Result<T, CoerceError>;
+    fn coerce_from(value: T) -> Result<Self, CoerceError> where Self: Sized;
+    /// Turns `self` into the dual of `value` seeded for differentiation, as
+    /// `coerce_from(value)?.execute_derivative()` would construct it.
+    ///
+    /// Solvers call this on the dual returned by the previous evaluation rather than building a
+    /// new one every iteration, so dual types with heap-allocated derivatives should override it
+    /// to overwrite their storage instead of replacing it.
+    fn reseed_in_place(&mut self, value: T) -> Result<(), CoerceError> where Self: Sized + Derivable<T> {
+        *self = Self::coerce_from(value)?.execute_derivative();
+        Ok(())
+    }
+}
+
+/// Constructs a dual number from a scalar with a chosen derivative seed.
+///
+/// `Coerceable::coerce_from` always seeds the derivative with zero, and `execute_derivative` with
+/// one. Seeding with `seed` instead makes the first derivative of `f(x)` the directional
+/// derivative `seed * f'(x)`, which is what sensitivity analyses and partial derivatives build on.
+pub trait DualFactory<T>: Coerceable<T> where T: DualNumFloat {
+    fn seeded(value: T, seed: T) -> Result<Self, CoerceError> where Self: Sized;
+}
+
+/// Why a value could not be converted between a scalar and a dual number type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoerceError {
+    /// The value is NaN or lies outside of the range of the target type
+    NotRepresentable
+}
+
+/// Lifts a scalar into the dual number type `N`, i.e. `N::coerce_from(value)`
+pub fn from_scalar<N, T>(value: T) -> Result<N, CoerceError> where N: Coerceable<T>, T: DualNumFloat {
+    N::coerce_from(value)
+}
+
+/// Reads the real part of a dual number back as a scalar, i.e. `value.coerce_to()`
+pub fn to_scalar<N, T>(value: &N) -> Result<T, CoerceError> where N: Coerceable<T>, T: DualNumFloat {
+    value.coerce_to()
+}
+
+/// Converts between the scalar type `T` and the dual number type `N`, naming both types once so
+/// that a call site reads `Dualize::<f64, Dual32>::to_dual(x)` rather than `N::coerce_from(x)`
+pub struct Dualize<T, N>(PhantomData<(T, N)>);
+
+impl <T: DualNumFloat, N: Coerceable<T>> Dualize<T, N> {
+    /// Lifts `value` into `N`, as `from_scalar` does
+    pub fn to_dual(value: T) -> Result<N, CoerceError> {
+        from_scalar(value)
+    }
+
+    /// Reads the real part of `value` back as a scalar, as `to_scalar` does
+    pub fn from_dual(value: &N) -> Result<T, CoerceError> {
+        to_scalar(value)
+    }
+}
+
+/// Converts a scalar to `f32`, failing for NaN or values outside of the range of `f32`
+fn to_f32<T: DualNumFloat>(value: T) -> Result<f32, CoerceError> {
+    match value.to_f32() {
+        Some(v) if !v.is_nan() && (v.is_finite() || value.is_infinite()) => Ok(v),
+        _ => Err(CoerceError::NotRepresentable)
+    }
 }
 
 impl Derivable<f32> for Dual32 {
     fn execute_derivative(&self) -> Self {
-        return self.derivative()
+        self.derivative()
     }
     fn zeroth_derivative(&self) -> f32 {
-        return self.re
+        self.re
     }
     fn first_derivative(&self) -> f32 {
-        return self.eps
+        self.eps
     }
 }
 
 impl <T: DualNumFloat> Coerceable<T> for Dual32 {
-    fn coerce_to(&self) -> T {
-        return T::from(self.re).unwrap()
+    fn coerce_to(&self) -> Result<T, CoerceError> {
+        T::from(self.re).ok_or(CoerceError::NotRepresentable)
+    }
+    fn coerce_from(value: T) -> Result<Self, CoerceError> {
+        Ok(Dual32::from_re(to_f32(value)?))
+    }
+    fn reseed_in_place(&mut self, value: T) -> Result<(), CoerceError> {
+        self.re = to_f32(value)?;
+        self.eps = 1.0;
+        Ok(())
+    }
+}
+
+impl <T: DualNumFloat> DualFactory<T> for Dual32 {
+    fn seeded(value: T, seed: T) -> Result<Self, CoerceError> {
+        Ok(Dual32::new(to_f32(value)?, to_f32(seed)?))
+    }
+}
+
+impl Derivable<f64> for Dual64 {
+    fn execute_derivative(&self) -> Self {
+        self.derivative()
+    }
+    fn zeroth_derivative(&self) -> f64 {
+        self.re
+    }
+    fn first_derivative(&self) -> f64 {
+        self.eps
+    }
+}
+
+impl <T: DualNumFloat> Coerceable<T> for Dual64 {
+    fn coerce_to(&self) -> Result<T, CoerceError> {
+        T::from(self.re).ok_or(CoerceError::NotRepresentable)
+    }
+    fn coerce_from(value: T) -> Result<Self, CoerceError> {
+        Ok(Dual64::from_re(value.to_f64().ok_or(CoerceError::NotRepresentable)?))
+    }
+    fn reseed_in_place(&mut self, value: T) -> Result<(), CoerceError> {
+        self.re = value.to_f64().ok_or(CoerceError::NotRepresentable)?;
+        self.eps = 1.0;
+        Ok(())
+    }
+}
+
+impl <T: DualNumFloat> DualFactory<T> for Dual64 {
+    fn seeded(value: T, seed: T) -> Result<Self, CoerceError> {
+        let value = value.to_f64().ok_or(CoerceError::NotRepresentable)?;
+        let seed = seed.to_f64().ok_or(CoerceError::NotRepresentable)?;
+        Ok(Dual64::new(value, seed))
+    }
+}
+
+/// A single dimensional `DualDVec32`, whose heap-allocated derivative makes it `Clone` but not `Copy`
+impl Derivable<f32> for DualDVec32 {
+    fn execute_derivative(&self) -> Self {
+        DualVec::new(self.re, Derivative::derivative_generic(Dyn(1), U1, 0))
+    }
+    fn zeroth_derivative(&self) -> f32 {
+        self.re
+    }
+    fn first_derivative(&self) -> f32 {
+        self.eps.clone().unwrap_generic(Dyn(1), U1)[0]
+    }
+}
+
+impl <T: DualNumFloat> Coerceable<T> for DualDVec32 {
+    fn coerce_to(&self) -> Result<T, CoerceError> {
+        T::from(self.re).ok_or(CoerceError::NotRepresentable)
+    }
+    fn coerce_from(value: T) -> Result<Self, CoerceError> {
+        Ok(DualVec::from_re(to_f32(value)?))
     }
-    fn coerce_from(value: T) -> Self {
-        return Dual32::from_re(value.to_f32().unwrap())
+    /// Reuses the derivative vector when it has one, which `from_re` leaves unallocated
+    fn reseed_in_place(&mut self, value: T) -> Result<(), CoerceError> {
+        self.re = to_f32(value)?;
+        let mut eps = std::mem::replace(&mut self.eps, Derivative::none()).unwrap_generic(Dyn(1), U1);
+        eps[0] = 1.0;
+        self.eps = Derivative::some(eps);
+        Ok(())
+    }
+}
+
+/// How the first derivative reported by a dual number type compares to a finite-difference estimate
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DerivativeCheck<T> where T: DualNumFloat {
+    /// The derivative reported through `Derivable::first_derivative`
+    pub reported: T,
+    /// The central difference estimate `(f(x + step) - f(x - step)) / (2 * step)`
+    pub estimated: T,
+    /// The absolute difference between the reported and estimated derivatives
+    pub discrepancy: T
+}
+
+/// Compares the derivative of `f` reported by the dual type `N` at `point` against a central
+/// difference of its zeroth derivative, to help validate custom `Derivable` implementations.
+pub fn check_derivative<F, N, T>(f: F, point: T, step: T) -> Result<DerivativeCheck<T>, CoerceError>
+where
+    F: Fn(N) -> N,
+    N: Derivable<T> + Coerceable<T>,
+    T: DualNumFloat
+{
+    let reported = f(from_scalar::<N, T>(point)?.execute_derivative()).first_derivative();
+    let above = f(from_scalar(point + step)?).zeroth_derivative();
+    let below = f(from_scalar(point - step)?).zeroth_derivative();
+    let estimated = (above - below) / (step + step);
+    Ok(DerivativeCheck{reported, estimated, discrepancy: (reported - estimated).abs()})
+}
+
+/// Checks a candidate root obtained elsewhere against the criteria of the search: either `f` changes
+/// sign across `[x - tolerance, x + tolerance]`, or `|f(x)|` is within the tolerance, as it is at a
+/// root where `f` only touches zero. A point that cannot be represented by `N` is not a root.
+pub fn is_root<F, N, T>(f: F, x: T, tolerance: T) -> bool
+where
+    F: Fn(N) -> N,
+    N: Derivable<T> + Coerceable<T>,
+    T: DualNumFloat
+{
+    let sample = |x: T| from_scalar::<N, T>(x).map(|x| f(x).zeroth_derivative());
+    match (sample(x - tolerance), sample(x), sample(x + tolerance)) {
+        (Ok(below), Ok(at), Ok(above)) => at.abs() <= tolerance || (below > T::zero()) != (above > T::zero()),
+        _ => false
     }
 }
 
+/// The rate `dr/dp` at which a root `r` of `f(x, p) = target` moves with the parameter `p`.
+///
+/// By the implicit function theorem this is `-(df/dp) / (df/dx)` at the root, with both partial
+/// derivatives read from a dual evaluation of `f` seeded in the matching direction.
+pub fn root_sensitivity<F, N, T>(f: F, root: T, parameter: T) -> Result<T, CoerceError>
+where
+    F: Fn(N, N) -> N,
+    N: Derivable<T> + DualFactory<T>,
+    T: DualNumFloat
+{
+    let dx = f(N::seeded(root, T::one())?, N::seeded(parameter, T::zero())?).first_derivative();
+    let dp = f(N::seeded(root, T::zero())?, N::seeded(parameter, T::one())?).first_derivative();
+    Ok(-dp / dx)
+}
+
+/// Composes `f` after `g` into `x -> f(g(x))`, which can be passed to any of the solvers.
+///
+/// Both functions take and return the same dual type, so the derivative of `g` is carried into `f`
+/// and the composition is differentiated by the chain rule.
+pub fn compose<F, G, N>(f: F, g: G) -> impl Fn(N) -> N + Sync + Send
+where
+    F: Fn(N) -> N + Sync + Send,
+    G: Fn(N) -> N + Sync + Send
+{
+    move |x| f(g(x))
+}
+
+/// A function defined by a data structure rather than a closure, such as an interpolator, a spline
+/// or a lookup table, which `root_search_evaluate` finds the roots of.
+///
+/// The function is evaluated in any dual number over `T`, so that it is differentiated as a closure
+/// would be, and may read whatever state the structure holds.
+pub trait Evaluate<T> where T: DualNumFloat {
+    fn eval<N: DualNum<T>>(&self, x: N) -> N;
+}
+
 pub struct NewtonOptions<T> where T: DualNumFloat {
     pub guess: T,
     pub patience: i32,
-    pub tolerance: T
+    pub tolerance: T,
+    /// The value the function is solved for, i.e. the solve finds `x` such that `f(x) = target`
+    pub target: T,
+    /// The largest distance a single step may move the iterate, however flat the function is there
+    pub max_step: Option<T>,
+    /// The number of iterations to take before a small step is accepted as convergence, so that a
+    /// solve starting out in a flat region does not stop before it has got anywhere
+    pub min_iterations: i32,
+    /// The residual `|f(x) - target|` below which the solve may converge, on top of the step falling
+    /// below `tolerance`. Infinite by default, which disables the check, see `Tolerance`.
+    pub residual_tolerance: T,
+    /// The step threshold relative to the magnitude of the iterate, which takes over from the
+    /// absolute `tolerance` wherever `relative_tolerance * |x|` is the larger of the two, as in the
+    /// error control of ODE solvers. Zero by default, which leaves only the absolute `tolerance`.
+    pub relative_tolerance: T
+}
+
+impl <T: DualNumFloat> NewtonOptions<T> {
+    /// Replaces the step and residual thresholds of the solve with those of `tolerance`
+    pub fn with_tolerance(self, tolerance: Tolerance<T>) -> Self {
+        NewtonOptions{tolerance: tolerance.step, residual_tolerance: tolerance.residual, ..self}
+    }
+
+    /// The step and residual thresholds of the solve
+    pub fn convergence(&self) -> Tolerance<T> {
+        Tolerance{step: self.tolerance, residual: self.residual_tolerance}
+    }
+
+    /// The step threshold at the iterate `current`, i.e. `max(tolerance, relative_tolerance * |current|)`
+    pub fn step_tolerance(&self, current: T) -> T {
+        self.tolerance.max(self.relative_tolerance * current.abs())
+    }
+}
+
+/// The thresholds that a Newton solve must meet to converge: both the step and the residual
+/// `|f(x) - target|` must fall below their thresholds.
+///
+/// A step alone can be tiny while the function is still far from the target, e.g. where it is
+/// steep or the iterates crawl across a flat region, and a residual alone says nothing of how
+/// precisely the root is located. A threshold of infinity disables its check.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance<T> where T: DualNumFloat {
+    pub step: T,
+    pub residual: T
+}
+
+impl <T: DualNumFloat> Tolerance<T> {
+    pub fn is_met(&self, step: T, residual: T) -> bool {
+        (step.abs() < self.step || self.step == T::infinity()) && (residual.abs() < self.residual || self.residual == T::infinity())
+    }
 }
 
 pub struct BisectionOptions<T> where T: DualNumFloat {
     pub lower: T,
     pub upper: T,
-    pub resolution: i32
+    pub resolution: i32,
+    /// The value the function is bracketed against, i.e. sign changes of `f(x) - target`
+    pub target: T,
+    /// How many times an interval without a sign change, but whose slope turns back towards zero,
+    /// is narrowed to look for a pair of roots hidden inside of it. Zero disables the search.
+    pub subdivisions: i32,
+    /// Samples of `f(x) - target` within this distance of zero are taken to be exactly zero, i.e.
+    /// roots on the grid, so that rounding noise of the wrong sign cannot move the bracket
+    pub sign_epsilon: T
 }
 
+#[derive(Clone, Copy)]
 pub struct RootSearchOptions<T> where T: DualNumFloat {
     pub patience: i32,
     pub tolerance: T,
     pub lower: T,
     pub upper: T,
-    pub resolution: i32
+    pub resolution: i32,
+    /// The value the function is solved for, i.e. the search finds all `x` such that `f(x) = target`
+    pub target: T,
+    /// Swap transposed bounds with a warning instead of panicking
+    pub auto_order_bounds: bool,
+    /// Keep roots that Newton converged to outside of the bracket it started in
+    pub keep_outside_bracket: bool,
+    /// Only solve the brackets whose sign change is in this direction, which leaves out every root
+    /// that the function only touches
+    pub direction: Option<Crossing>,
+    /// A symmetry of the function that lets only the non-negative half of the bounds be scanned
+    pub symmetry: Symmetry,
+    /// See `BisectionOptions::subdivisions`
+    pub subdivisions: i32,
+    /// See `BisectionOptions::sign_epsilon`
+    pub sign_epsilon: T,
+    /// Report `lower` and `upper` as roots when the function is within the tolerance of the target
+    /// there, as a root on a bound cannot lie strictly inside of any bracket. Off by default.
+    pub boundary_roots: bool,
+    /// Keep only this many roots, the lowest of them by position. Brackets are solved in ascending
+    /// order and the search stops once it has found enough of them. Every search respects it but
+    /// `root_search_resumable` and `resume_search`, as a cursor knows nothing of the roots before it.
+    pub max_total_roots: Option<usize>,
+    /// What to do with a bracket in which no root could be found
+    pub on_failure: OnFailure,
+    /// Keep searching the rest of a bracket for further roots once one has been found in it, as a
+    /// coarse grid can lump an odd number of roots into a single bracket. This solves both sides of
+    /// every root again, so it is many times slower.
+    pub exhaust_brackets: bool,
+    /// Accept a root that Newton's method converges to exactly on an end of its bracket, rather
+    /// than only those strictly inside of it
+    pub inclusive_bounds: bool,
+    /// Report the midpoint of every run of grid samples over which `|f - target|` stays below this
+    /// tolerance as a plateau, unless a root was found within it. Unlike a sign change, a plateau
+    /// only shows that `f` is indistinguishable from the target there, e.g. where it grazes the
+    /// target or rounding hides a true crossing, so plateaus are kept apart from the roots.
+    pub plateau_tolerance: Option<T>,
+    /// Round every root to this many decimal places once the search is done, see `round_to_decimals`.
+    /// This only tidies the roots for display and comparison, and does not make them any more precise.
+    /// Brackets, poles and plateaus are left as they are.
+    pub round_to_decimals: Option<i32>
+}
+
+/// What a root search does with a bracket whose Newton solves fail to converge to a root inside of it.
+///
+/// The guesses of a bracket are tried in order and the first one whose solve fails outright, e.g.
+/// by diverging, abandons the remaining guesses, so it is the bracket as a whole that fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnFailure {
+    /// Move on to the next bracket
+    Skip,
+    /// Solve the bracket again with `newton_bisection`, which converges whenever it holds a sign change
+    Fallback,
+    /// Stop the search, keeping only the roots of the brackets below the one that failed
+    Abort
+}
+
+/// A symmetry of a function about zero, which the root search can exploit to halve its work
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    /// No symmetry is assumed
+    None,
+    /// `f(-x) = f(x)`
+    Even,
+    /// `f(-x) = -f(x)`, so that `f(0) = 0`
+    Odd
+}
+
+pub struct HybridOptions<T> where T: DualNumFloat {
+    pub lower: T,
+    pub upper: T,
+    pub patience: i32,
+    pub tolerance: T,
+    pub target: T
+}
+
+impl <T: DualNumFloat> Default for NewtonOptions<T> {
+    fn default() -> Self {
+        NewtonOptions{
+            guess: T::zero(),
+            patience: 1000,
+            tolerance: T::from(0.0001).unwrap(),
+            target: T::zero(),
+            max_step: None,
+            min_iterations: 0,
+            residual_tolerance: T::infinity(),
+            relative_tolerance: T::zero()
+        }
+    }
+}
+
+impl <T: DualNumFloat> Default for BisectionOptions<T> {
+    fn default() -> Self {
+        BisectionOptions{
+            lower: T::zero(),
+            upper: T::one(),
+            resolution: 1000,
+            target: T::zero(),
+            subdivisions: 0,
+            sign_epsilon: T::zero()
+        }
+    }
+}
+
+impl <T: DualNumFloat> Default for HybridOptions<T> {
+    fn default() -> Self {
+        HybridOptions{
+            lower: T::zero(),
+            upper: T::one(),
+            patience: 1000,
+            tolerance: T::from(0.0001).unwrap(),
+            target: T::zero()
+        }
+    }
+}
+
+impl <T: DualNumFloat> Default for RootSearchOptions<T> {
+    fn default() -> Self {
+        RootSearchOptions{
+            patience: 1000,
+            tolerance: T::from(0.0001).unwrap(),
+            lower: T::zero(),
+            upper: T::one(),
+            resolution: 1000,
+            target: T::zero(),
+            auto_order_bounds: false,
+            keep_outside_bracket: false,
+            direction: None,
+            symmetry: Symmetry::None,
+            subdivisions: 0,
+            sign_epsilon: T::zero(),
+            boundary_roots: false,
+            max_total_roots: None,
+            on_failure: OnFailure::Skip,
+            exhaust_brackets: false,
+            inclusive_bounds: false,
+            plateau_tolerance: None,
+            round_to_decimals: None
+        }
+    }
+}
+
+impl <T: DualNumFloat> RootSearchOptions<T> {
+    /// The options of the Newton solve that a search starts from `guess`, i.e. its patience,
+    /// tolerance and target, leaving every other option of the solve at its default
+    pub fn newton_options(&self, guess: T) -> NewtonOptions<T> {
+        NewtonOptions{
+            guess,
+            patience: self.patience,
+            tolerance: self.tolerance,
+            target: self.target,
+            ..Default::default()
+        }
+    }
+
+    /// The options of the grid scan over the bounds, i.e. its resolution, target, subdivisions and
+    /// sign epsilon
+    pub fn bisection_options(&self) -> BisectionOptions<T> {
+        BisectionOptions{
+            lower: self.lower,
+            upper: self.upper,
+            resolution: self.resolution,
+            target: self.target,
+            subdivisions: self.subdivisions,
+            sign_epsilon: self.sign_epsilon
+        }
+    }
+
+    /// `root` rounded to `round_to_decimals`, as every root returned by a search is
+    pub(crate) fn rounded(&self, root: T) -> T {
+        self.round_to_decimals.map_or(root, |decimals| round_to_decimals(root, decimals))
+    }
+}
+
+/// The reason a Newton solve stopped iterating
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum TerminationReason {
+    /// The step size fell below the tolerance
+    ToleranceMet,
+    /// The iteration limit was reached before the step size fell below the tolerance
+    PatienceExhausted,
+    /// The derivative vanished so no Newton step could be taken
+    ZeroDerivative,
+    /// An iterate was no longer a finite number
+    Diverged,
+    /// An iterate could not be kept inside of the domain of the function
+    OutsideDomain,
+    /// A step would have taken the iterate beyond the bounds of a solve that treats them as walls
+    LeftDomain,
+    /// The function does not change sign across the bracket it was asked to solve in
+    NotBracketed,
+    /// The evaluation budget ran out before the step size fell below the tolerance
+    BudgetExhausted,
+    /// An iterate could not be converted into the dual number type
+    NotRepresentable,
+    /// The residual stopped improving before the step size fell below the tolerance
+    Stagnated,
+    /// The guess, bounds or tolerance given to the solve were not finite numbers
+    NonFinite,
+    /// The iterates alternated between two points
+    Cycling,
+    /// The step fell to the spacing of the floats around the root without falling below a tolerance
+    /// smaller than that spacing. The root is as precise as the float type allows.
+    ToleranceUnachievable
 }
 
 pub struct NewtonResult<T> where T: DualNumFloat {
     pub root: Option<T>,
-    pub iterations: i32
+    pub iterations: i32,
+    pub termination: TerminationReason
+}
+
+/// The direction in which a function crosses zero
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Crossing {
+    /// The function goes from negative to positive
+    Rising,
+    /// The function goes from positive to negative
+    Falling,
+    /// The function touches zero on a grid point without changing sign, as at a double root
+    Touching
 }
 
-pub struct BisectionResult<T> where T: DualNumFloat {
+/// A closed interval `[lower, upper]`, such as a bracket around a root
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Interval<T> where T: DualNumFloat {
     pub lower: T,
-    pub upper: T,
+    pub upper: T
+}
+
+impl <T: DualNumFloat> Interval<T> {
+    pub fn new(lower: T, upper: T) -> Self {
+        Interval{lower, upper}
+    }
+
+    pub fn midpoint(&self) -> T {
+        (self.lower + self.upper) / T::from(2).unwrap()
+    }
+
+    pub fn width(&self) -> T {
+        self.upper - self.lower
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        self.lower <= x && x <= self.upper
+    }
+
+    /// The direction in which `f` crosses zero between the endpoints, if its sign changes
+    pub fn crossing<F, N>(&self, f: F) -> Option<Crossing>
+    where
+        F: Fn(N) -> N,
+        N: Derivable<T> + Coerceable<T>
+    {
+        let flower = f(from_scalar(self.lower).ok()?).zeroth_derivative();
+        let fupper = f(from_scalar(self.upper).ok()?).zeroth_derivative();
+        if flower < T::zero() && fupper > T::zero() {
+            Some(Crossing::Rising)
+        } else if flower > T::zero() && fupper < T::zero() {
+            Some(Crossing::Falling)
+        } else {
+            None
+        }
+    }
+
+    pub fn has_sign_change<F, N>(&self, f: F) -> bool
+    where
+        F: Fn(N) -> N,
+        N: Derivable<T> + Coerceable<T>
+    {
+        self.crossing(f).is_some()
+    }
 }
 
 pub struct RootSearchResult<T> where T: DualNumFloat {
     pub roots: Vec<T>,
-    pub bisections: Vec<BisectionResult<T>>,
+    pub bisections: Vec<Interval<T>>,
+    /// The direction of the sign change each root was bracketed by, in the same order as `roots`
+    pub crossings: Vec<Crossing>,
+    /// Roots that were found outside of their originating bracket, if these were asked to be kept
+    pub outside_bracket_roots: Vec<T>,
+    /// Brackets whose sign change is a pole of the function rather than a root, which are not solved
+    pub poles: Vec<Interval<T>>,
+    /// The midpoints of the plateaus found with `RootSearchOptions::plateau_tolerance`
+    pub plateaus: Vec<T>
+}
+
+impl <T: DualNumFloat> RootSearchResult<T> {
+    /// A result without any roots, brackets, poles or plateaus, as returned for a flat function
+    pub fn empty() -> Self {
+        RootSearchResult{
+            roots: Vec::new(),
+            bisections: Vec::new(),
+            crossings: Vec::new(),
+            outside_bracket_roots: Vec::new(),
+            poles: Vec::new(),
+            plateaus: Vec::new()
+        }
+    }
+}
+
+/// Why solving a bracket did not produce a root inside of it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RootError<T> where T: DualNumFloat {
+    /// Newton's method converged, but only to a root outside of the bracket
+    OutsideBracket(T),
+    /// Newton's method did not converge from the initial guesses
+    NotConverged
+}
+
+/// Why a root search could not be carried out over the requested range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RootSearchError {
+    /// The lower bound is greater than the upper bound
+    BoundsReversed,
+    /// The lower and upper bounds are the same
+    BoundsEqual,
+    /// Every grid sample of the function lies within tolerance of every other one
+    FlatFunction,
+    /// A bound or the tolerance is NaN or infinite
+    NonFinite,
+    /// The range holds more periods of a periodic function than can be enumerated, see `PeriodicRoots::within`
+    TooManyPeriods
+}
+
+impl fmt::Display for RootSearchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RootSearchError::BoundsReversed => write!(f, "Lower bound must be less than upper bound"),
+            RootSearchError::BoundsEqual => write!(f, "Bounds cannot be the same"),
+            RootSearchError::FlatFunction => write!(f, "Function is flat over the search range"),
+            RootSearchError::NonFinite => write!(f, "Bounds and tolerance must be finite"),
+            RootSearchError::TooManyPeriods => write!(f, "Range spans too many periods to enumerate")
+        }
+    }
+}
+
+/// Checks that the bounds and tolerance are finite, swaps transposed bounds if asked to, then
+/// checks that the bounds describe a non-empty range
+pub(crate) fn validate_bounds<T>(opts: &mut RootSearchOptions<T>) -> Result<(), RootSearchError> where T: DualNumFloat {
+    if !(opts.lower.is_finite() && opts.upper.is_finite() && opts.tolerance.is_finite()) {
+        return Err(RootSearchError::NonFinite);
+    }
+    if opts.lower > opts.upper && opts.auto_order_bounds {
+        log::warn!("Lower bound {} is greater than upper bound {}, swapping them", opts.lower, opts.upper);
+        std::mem::swap(&mut opts.lower, &mut opts.upper);
+    }
+    if opts.lower > opts.upper {
+        return Err(RootSearchError::BoundsReversed);
+    }
+    if opts.lower == opts.upper {
+        return Err(RootSearchError::BoundsEqual);
+    }
+    Ok(())
+}
+
+/// Returns `opts` once `validate_bounds` has accepted them, panicking with its error otherwise,
+/// as every search that does not return a `Result` does
+pub(crate) fn validated<T>(mut opts: RootSearchOptions<T>) -> RootSearchOptions<T> where T: DualNumFloat {
+    if let Err(error) = validate_bounds(&mut opts) {
+        panic!("{}", error)
+    }
+    opts
 }
 
-fn newton<'a, F, N, T>(f: F, opts: NewtonOptions<T>) -> NewtonResult<T>
+pub fn newton<'a, F, N, T>(f: F, opts: NewtonOptions<T>) -> NewtonResult<T>
 where
     F: Fn(N) -> N + Send + Sync + 'a,
-    N: Derivable<T> + Coerceable<T> + Display + Copy,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
     T: DualNumFloat
 {
-    let mut current: T = opts.guess;
-    let mut count = 0;
-    let debug_env = env::var("DEBUG");
-    let debug = match debug_env {
-        Ok(val) => val == "true",
-        Err(_) => false
-    };
-    loop {
-        count += 1;
-        let x = N::coerce_from(current).execute_derivative();
-        let z = f(x);
-        let next = x.zeroth_derivative() - z.zeroth_derivative() / z.first_derivative();
-        let diff = next - current;
-        if diff.abs() < opts.tolerance {
-            if debug {
-                println!("Found root at: {}", next);
-            }
-            return NewtonResult{
-                root: Some(next),
-                iterations: count
-            };
-        } else {
-            if count > opts.patience {
-                if debug {
-                    println!("Failed to find root with initial guess of {}", opts.guess);
-                    println!("Last iteration was: {}", current);
-                    println!("Try updating the initial guess or increasing the tolerance or patience");
-                }
-                return NewtonResult{
-                    root: None,
-                    iterations: count
-                };
-            }
-            current = next;
-        }
-    }
+    newton_in_domain(f, |_| true, opts)
 }
 
-fn find_bisections<F, N, T>(f: F, opts: BisectionOptions<T>) -> Vec<BisectionResult<T>>
+/// Newton's method that stops once `|f(x) - target|` falls below `residual_tolerance`, however
+/// large the step still is, to solve the equation to a given accuracy rather than to locate the
+/// root to one. The tolerance of `opts` is replaced.
+pub fn newton_to_residual<F, N, T>(f: F, residual_tolerance: T, opts: NewtonOptions<T>) -> NewtonResult<T>
 where
-    F: Fn(N) -> N + Sync + Send + Copy,
-    N: Derivable<T> + Coerceable<T> + Display + Copy + Sub + Div,
+    F: Fn(N) -> N + Send + Sync,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
     T: DualNumFloat
 {
-    let step = (opts.upper - opts.lower) / T::from(opts.resolution).unwrap() + T::epsilon();
-    // Add off-set to step to deal with roots at middle of lower and upper range
-    let mut values: Vec<BisectionResult<T>> = Vec::new();
-
-    for i in 0..opts.resolution {
-        let a = opts.lower + step * T::from(i).unwrap();
-        let b = opts.lower + step * T::from(i+1).unwrap();
-        let fa = f(N::coerce_from(a));
-        let fb = f(N::coerce_from(b));
-        let pos2neg = fa.zeroth_derivative() > T::zero() && fb.zeroth_derivative() < T::zero();
-        let neg2pos = fa.zeroth_derivative() < T::zero() && fb.zeroth_derivative() > T::zero();
-        if pos2neg || neg2pos {
-            values.push(BisectionResult{lower: a, upper: b});
-        }
-    };
-    values
+    newton(f, opts.with_tolerance(Tolerance{step: T::infinity(), residual: residual_tolerance}))
 }
 
-pub fn root_search<F, N, T>(f: F, opts: RootSearchOptions<T>) -> RootSearchResult<T>
+/// Refines each of the estimated roots in `candidates` with Newton's method, using the candidate
+/// in place of the guess of `opts`.
+///
+/// The results are in the same order as the candidates, with `None` for those that failed to converge.
+pub fn refine_candidates<F, N, T>(f: F, candidates: &[T], opts: NewtonOptions<T>) -> Vec<Option<T>>
 where
-    F: Fn(N) -> N + Sync + Send + Copy,
-    N: Derivable<T> + Coerceable<T> + Display + Copy + Sub + Div,
+    F: Fn(N) -> N + Send + Sync,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
     T: DualNumFloat
 {
-    if opts.lower > opts.upper {
-        panic!("Lower bound must be greater than upper bound")
+    candidates.iter()
+        .map(|&guess| newton(&f, NewtonOptions{guess, ..opts}).root)
+        .collect()
+}
+
+/// A configured Newton solver that can be reused across many functions and initial guesses
+#[derive(Debug, Clone, Copy)]
+pub struct Newton<T> where T: DualNumFloat {
+    patience: i32,
+    tolerance: T,
+    starts: i32
+}
+
+impl <T: DualNumFloat> Newton<T> {
+    pub fn new() -> Self {
+        Newton{
+            patience: 1000,
+            tolerance: T::from(0.0001).unwrap(),
+            starts: 100
+        }
     }
-    if opts.lower == opts.upper {
-        panic!("Bounds cannot be the same")
+
+    /// Sets the number of evenly spaced initial guesses used when solving over a range as a `Solver`
+    pub fn with_starts(mut self, starts: i32) -> Self {
+        self.starts = starts;
+        self
     }
-    let bisections = find_bisections(f, BisectionOptions{
-        lower: opts.lower,
-        upper: opts.upper,
-        resolution: opts.resolution
-    });
-    let mut roots: Vec<T> = Vec::new();
-    for bisection in &bisections {
-        let res = T::from(100).unwrap();
-        let step = (bisection.upper - bisection.lower) / res;
-        for i in 0..res.to_i32().unwrap() {
-            let guess = bisection.lower + (T::from(i).unwrap() * step);
-            let res = newton(f, NewtonOptions{
-                guess: guess,
-                patience: opts.patience,
-                tolerance: opts.tolerance
-            });
-            if res.root.is_none() {
-                break;
-            }
-            let root = res.root.unwrap();
-            if bisection.lower < root && root < bisection.upper {
-                roots.push(root);
-                break;
-            }
-        }
 
+    pub fn with_patience(mut self, patience: i32) -> Self {
+        self.patience = patience;
+        self
     }
-    RootSearchResult{roots, bisections}
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use num_dual::{Dual32, DualNum};
+    pub fn with_tolerance(mut self, tolerance: T) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
 
-    #[test]
-    fn find_sine_root_newton() {
-        fn sine<D: DualNum<f32>>(x: D) -> D {
-            x.sin()
+    pub fn options(&self, guess: T) -> NewtonOptions<T> {
+        NewtonOptions{
+            guess,
+            patience: self.patience,
+            tolerance: self.tolerance,
+            ..Default::default()
         }
-        let res = newton::<_,Dual32,f32>(&sine, NewtonOptions{
-            guess: 2.0,
-            patience: 1000,
-            tolerance: 0.0001
-        });
-        assert_eq!(std::f32::consts::PI, res.root.unwrap())
     }
 
-    #[test]
-    fn find_cosine_root_newton() {
-        fn cosine<D: DualNum<f32>>(x: D) -> D {
-            x.cos()
-        }
-        let res = newton::<_,Dual32,f32>(&cosine, NewtonOptions{
-            guess: 2.0,
-            patience: 1000,
-            tolerance: 0.0001
-        });
-        assert_eq!(std::f32::consts::PI / 2.0, res.root.unwrap())
+    pub fn solve<F, N>(&self, f: F, guess: T) -> NewtonResult<T>
+    where
+        F: Fn(N) -> N + Send + Sync,
+        N: Derivable<T> + Coerceable<T> + Display + Clone
+    {
+        newton(f, self.options(guess))
     }
 
-    #[test]
-    fn find_sine_bisections() {
-        fn sine<D: DualNum<f32>>(x: D) -> D {
-            x.sin()
-        }
-        let bisections = find_bisections::<_,Dual32,f32>(&sine, BisectionOptions{
-            lower: -5.0, 
-            upper: 5.0, 
-            resolution: 1000
-        });
-        for bisection in &bisections {
-            println!("bisection: ({},{})", bisection.lower, bisection.upper)
-        }
-        assert_eq!(bisections.len(), 3)
+    pub fn solve_in_domain<F, D, N>(&self, f: F, domain: D, guess: T) -> NewtonResult<T>
+    where
+        F: Fn(N) -> N + Send + Sync,
+        D: Fn(T) -> bool,
+        N: Derivable<T> + Coerceable<T> + Display + Clone
+    {
+        newton_in_domain(f, domain, self.options(guess))
     }
 
-    #[test]
+    /// Estimates the basin of attraction of `root` by probing outwards from it in increments of
+    /// `step`, up to `max_radius` either side.
+    ///
+    /// Each side of the returned interval is the furthest probe reached before one fails to
+    /// converge back to within the tolerance of `root`.
+    pub fn basin<F, N>(&self, f: F, root: T, step: T, max_radius: T) -> Interval<T>
+    where
+        F: Fn(N) -> N + Send + Sync,
+        N: Derivable<T> + Coerceable<T> + Display + Clone
+    {
+        let probes = (max_radius / step).floor().to_i32().unwrap_or(0);
+        let reach = |direction: T| {
+            let mut reached = T::zero();
+            for i in 1..=probes {
+                let offset = step * T::from(i).unwrap();
+                match self.solve(&f, root + direction * offset).root {
+                    Some(found) if (found - root).abs() <= self.tolerance => reached = offset,
+                    _ => break
+                }
+            }
+            reached
+        };
+        Interval::new(root - reach(-T::one()), root + reach(T::one()))
+    }
+}
+
+impl <T: DualNumFloat> Default for Newton<T> {
+    fn default() -> Self {
+        Newton::new()
+    }
+}
+
+/// A method of finding the roots of a function within a range
+pub trait Solver<F, N, T> where T: DualNumFloat {
+    fn find_roots(&self, f: F, lower: T, upper: T) -> Vec<T>;
+}
+
+/// Runs Newton's method from evenly spaced initial guesses across the range, keeping every root
+/// that lands inside of it. Unlike a bracketing search this also finds roots where the function
+/// touches zero without changing sign.
+impl <F, N, T> Solver<F, N, T> for Newton<T>
+where
+    F: Fn(N) -> N + Send + Sync,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
+    T: DualNumFloat
+{
+    fn find_roots(&self, f: F, lower: T, upper: T) -> Vec<T> {
+        let step = (upper - lower) / T::from(self.starts).unwrap();
+        let mut roots = Vec::new();
+        for i in 0..=self.starts {
+            if let Some(root) = self.solve(&f, lower + step * T::from(i).unwrap()).root {
+                if lower <= root && root <= upper {
+                    roots.push(root);
+                }
+            }
+        }
+        dedup_roots(roots, self.tolerance)
+    }
+}
+
+/// Runs `root_search` over the range, replacing the bounds of the options
+impl <F, N, T> Solver<F, N, T> for RootSearchOptions<T>
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    fn find_roots(&self, f: F, lower: T, upper: T) -> Vec<T> {
+        root_search(f, RootSearchOptions{lower, upper, ..*self}).roots
+    }
+}
+
+/// A stateful solver for a family of slowly varying problems, such as a parameter sweep.
+///
+/// The root found by the previous solve is used as the initial guess for the next one. Only if
+/// Newton's method fails from there, or converges outside of the search bounds, does the solver
+/// fall back to a full `root_search` over the bounds, taking the root closest to the previous one.
+pub struct WarmStart<T> where T: DualNumFloat {
+    pub newton: Newton<T>,
+    pub search: RootSearchOptions<T>,
+    last: Option<T>
+}
+
+impl <T: DualNumFloat> WarmStart<T> {
+    pub fn new(newton: Newton<T>, search: RootSearchOptions<T>) -> Self {
+        WarmStart{newton, search, last: None}
+    }
+
+    pub fn last_root(&self) -> Option<T> {
+        self.last
+    }
+
+    pub fn solve<F, N>(&mut self, f: F) -> Option<T>
+    where
+        F: Fn(N) -> N + Sync + Send,
+        N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div
+    {
+        if let Some(guess) = self.last {
+            if let Some(root) = self.newton.solve(&f, guess).root {
+                if self.search.lower <= root && root <= self.search.upper {
+                    self.last = Some(root);
+                    return self.last;
+                }
+            }
+        }
+        let roots = root_search(&f, self.search).roots;
+        let closest = match self.last {
+            Some(last) => roots.into_iter().min_by(|a, b| (*a - last).abs().partial_cmp(&(*b - last).abs()).unwrap()),
+            None => roots.into_iter().next()
+        };
+        if closest.is_some() {
+            self.last = closest;
+        }
+        closest
+    }
+}
+
+/// Sorts the roots and collapses every run of roots lying within `tolerance` of its neighbour
+/// into the first root of the run.
+pub fn dedup_roots<T>(mut roots: Vec<T>, tolerance: T) -> Vec<T> where T: DualNumFloat {
+    roots.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mut unique: Vec<T> = Vec::new();
+    let mut last: Option<T> = None;
+    for root in roots {
+        match last {
+            Some(previous) if root - previous <= tolerance => {},
+            _ => unique.push(root)
+        }
+        last = Some(root);
+    }
+    unique
+}
+
+/// The tightest interval `[min_root, max_root]` containing every one of `roots`, e.g. to set the
+/// axes of a plot, or `None` if there are no roots. The roots need not be sorted.
+pub fn roots_hull<T>(roots: &[T]) -> Option<Interval<T>> where T: DualNumFloat {
+    let first = *roots.first()?;
+    Some(roots.iter().fold(Interval::new(first, first), |hull, root| Interval::new(hull.lower.min(*root), hull.upper.max(*root))))
+}
+
+/// Runs every solver over the range and merges their roots, deduplicating within `tolerance`
+pub fn solve_all<F, N, T>(solvers: &[&dyn Solver<F, N, T>], f: F, lower: T, upper: T, tolerance: T) -> Vec<T>
+where
+    F: Clone,
+    T: DualNumFloat
+{
+    let mut roots = Vec::new();
+    for solver in solvers {
+        roots.extend(solver.find_roots(f.clone(), lower, upper));
+    }
+    dedup_roots(roots, tolerance)
+}
+
+/// The number of times a step is halved while trying to bring an iterate back into the domain
+const MAX_DOMAIN_HALVINGS: i32 = 64;
+
+/// The number of consecutive iterations without improvement after which a solve has stagnated
+const STAGNATION_WINDOW: i32 = 10;
+
+/// The factor by which the residual must shrink below its best value to count as an improvement
+const STAGNATION_FACTOR: f64 = 0.99;
+
+/// The number of floats around an iterate by which rounding alone may move it
+const ROUNDING_SPACINGS: f64 = 4.0;
+
+/// Newton's method for functions that are only defined where `domain` returns `true`.
+///
+/// If a step would take the iterate outside of the domain, the step is repeatedly halved
+/// until the iterate lands back inside it, so `f` is never evaluated at an invalid point.
+pub fn newton_in_domain<'a, F, D, N, T>(f: F, domain: D, opts: NewtonOptions<T>) -> NewtonResult<T>
+where
+    F: Fn(N) -> N + Send + Sync + 'a,
+    D: Fn(T) -> bool,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
+    T: DualNumFloat
+{
+    newton_dual_in_domain(f, domain, opts).0
+}
+
+/// Newton's method within the hard walls `[lower, upper]`, for an `f` that is only valid there.
+///
+/// Unlike `newton_in_domain`, a step that would leave the bounds is not damped back into them:
+/// the solve stops with `LeftDomain` instead, so `f` is never evaluated outside of them. A guess
+/// outside of the bounds is reported as `OutsideDomain`.
+pub fn solve_until_converged_or_bounds<F, N, T>(f: F, lower: T, upper: T, opts: NewtonOptions<T>) -> NewtonResult<T>
+where
+    F: Fn(N) -> N + Send + Sync,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
+    T: DualNumFloat
+{
+    // Once an iterate has left, the whole domain is closed so that the step cannot be damped
+    let left = Cell::new(false);
+    let res = newton_in_domain(f, |x| {
+        left.set(left.get() || !(lower <= x && x <= upper));
+        !left.get()
+    }, opts);
+    if left.get() && res.iterations > 0 {
+        NewtonResult{root: None, iterations: res.iterations, termination: TerminationReason::LeftDomain}
+    } else {
+        res
+    }
+}
+
+/// Newton's method that also returns the dual evaluation of `f` at the final iterate.
+///
+/// The dual carries the residual and the derivative at the root, so quantities such as the
+/// slope can be read with `first_derivative` without evaluating `f` again.
+pub fn newton_dual<F, N, T>(f: F, opts: NewtonOptions<T>) -> (NewtonResult<T>, Option<N>)
+where
+    F: Fn(N) -> N + Send + Sync,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
+    T: DualNumFloat
+{
+    newton_dual_in_domain(f, |_| true, opts)
+}
+
+/// A Newton solve along with the history of its iterates and residuals
+pub struct VerboseNewtonResult<T> where T: DualNumFloat {
+    pub result: NewtonResult<T>,
+    /// The iterates at which `f` was evaluated, starting from the guess
+    pub iterates: Vec<T>,
+    /// The value of `f(x) - target` at each of the iterates
+    pub residuals: Vec<T>
+}
+
+/// Newton's method that also records every iterate and its residual, e.g. to study its convergence.
+///
+/// Recording allocates, so the history is kept out of `newton` itself.
+pub fn newton_verbose<F, N, T>(f: F, opts: NewtonOptions<T>) -> VerboseNewtonResult<T>
+where
+    F: Fn(N) -> N + Send + Sync,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
+    T: DualNumFloat
+{
+    let history = Mutex::new(Vec::new());
+    let target = opts.target;
+    let recorded = |x: N| {
+        let iterate = x.zeroth_derivative();
+        let z = f(x);
+        history.lock().unwrap().push((iterate, z.zeroth_derivative() - target));
+        z
+    };
+    let result = newton(recorded, opts);
+    let (iterates, residuals) = history.into_inner().unwrap().into_iter().unzip();
+    VerboseNewtonResult{result, iterates, residuals}
+}
+
+/// A Newton solve along with every point it visited
+pub struct NewtonPath<T> where T: DualNumFloat {
+    pub result: NewtonResult<T>,
+    /// The iterates in the order they were visited, starting from the guess and ending at the root
+    /// when one was found
+    pub iterates: Vec<T>
+}
+
+/// Newton's method that also records every iterate, e.g. to animate its walk towards the root.
+///
+/// Like `newton_verbose`, the history is kept out of `newton` itself so that plain solves do not allocate.
+pub fn newton_path<F, N, T>(f: F, opts: NewtonOptions<T>) -> NewtonPath<T>
+where
+    F: Fn(N) -> N + Send + Sync,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
+    T: DualNumFloat
+{
+    let iterates = Mutex::new(Vec::new());
+    let recorded = |x: N| {
+        iterates.lock().unwrap().push(x.zeroth_derivative());
+        f(x)
+    };
+    let result = newton(recorded, opts);
+    let mut iterates = iterates.into_inner().unwrap();
+    iterates.extend(result.root);
+    NewtonPath{result, iterates}
+}
+
+fn newton_dual_in_domain<F, D, N, T>(f: F, domain: D, opts: NewtonOptions<T>) -> (NewtonResult<T>, Option<N>)
+where
+    F: Fn(N) -> N + Send + Sync,
+    D: Fn(T) -> bool,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
+    T: DualNumFloat
+{
+    let mut current: T = opts.guess;
+    let mut count = 0;
+    let mut best_residual = T::infinity();
+    let mut stalled = 0;
+    let mut last_step = T::infinity();
+    // The iterate before `current`, which a 2-cycle keeps returning to
+    let mut previous: Option<T> = None;
+    let stagnation_factor = T::from(STAGNATION_FACTOR).unwrap();
+    let debug_env = env::var("DEBUG");
+    let debug = match debug_env {
+        Ok(val) => val == "true",
+        Err(_) => false
+    };
+    // Only the step threshold may be infinite, as long as the residual threshold stops the solve
+    let step_disabled = opts.tolerance == T::infinity() && opts.residual_tolerance.is_finite();
+    let finite_tolerance = (opts.tolerance.is_finite() || step_disabled) && opts.relative_tolerance.is_finite();
+    if !(opts.guess.is_finite() && finite_tolerance) || opts.residual_tolerance.is_nan() {
+        return (NewtonResult{
+            root: None,
+            iterations: count,
+            termination: TerminationReason::NonFinite
+        }, None);
+    }
+    if !domain(current) {
+        if debug {
+            println!("Initial guess of {} lies outside of the domain", opts.guess);
+        }
+        return (NewtonResult{
+            root: None,
+            iterations: count,
+            termination: TerminationReason::OutsideDomain
+        }, None);
+    }
+    // The dual returned by the last evaluation, reseeded as the argument of the next
+    let mut spare: Option<N> = None;
+    loop {
+        count += 1;
+        let seeded = match spare.take() {
+            Some(mut x) => x.reseed_in_place(current).map(|_| x),
+            None => from_scalar::<N, T>(current).map(|x| x.execute_derivative())
+        };
+        let x = match seeded {
+            Ok(x) => x,
+            Err(_) => return (NewtonResult{
+                root: None,
+                iterations: count,
+                termination: TerminationReason::NotRepresentable
+            }, None)
+        };
+        let z = f(x);
+        if z.first_derivative() == T::zero() {
+            if debug {
+                println!("Derivative vanished at: {}", current);
+            }
+            return (NewtonResult{
+                root: None,
+                iterations: count,
+                termination: TerminationReason::ZeroDerivative
+            }, Some(z));
+        }
+        let mut next = current - (z.zeroth_derivative() - opts.target) / z.first_derivative();
+        if let Some(max_step) = opts.max_step {
+            if (next - current).abs() > max_step {
+                next = current + max_step.copysign(next - current);
+            }
+        }
+        if !next.is_finite() {
+            if debug {
+                println!("Diverged after iterating from: {}", current);
+            }
+            return (NewtonResult{
+                root: None,
+                iterations: count,
+                termination: TerminationReason::Diverged
+            }, Some(z));
+        }
+        let mut halvings = 0;
+        while !domain(next) {
+            if halvings >= MAX_DOMAIN_HALVINGS {
+                if debug {
+                    println!("Failed to damp the step from {} back into the domain", current);
+                }
+                return (NewtonResult{
+                    root: None,
+                    iterations: count,
+                    termination: TerminationReason::OutsideDomain
+                }, Some(z));
+            }
+            next = current + (next - current) / T::from(2).unwrap();
+            halvings += 1;
+        }
+        let diff = next - current;
+        // A step of exactly zero lands on the root itself, so only it is accepted before the minimum
+        let settled = count >= opts.min_iterations || diff == T::zero();
+        let tolerance = opts.step_tolerance(current);
+        let convergence = Tolerance{step: tolerance, ..opts.convergence()};
+        if settled && convergence.is_met(diff, z.zeroth_derivative() - opts.target) {
+            if debug {
+                println!("Found root at: {}", next);
+            }
+            return (NewtonResult{
+                root: Some(next),
+                iterations: count,
+                termination: TerminationReason::ToleranceMet
+            }, Some(z));
+        } else {
+            // Close enough to the root, rounding alone moves the iterate by a few floats, so a
+            // tolerance below the spacing of the floats there can never be met
+            let spacing = T::epsilon() * next.abs() * T::from(ROUNDING_SPACINGS).unwrap();
+            let residual_met = Tolerance{step: T::infinity(), ..opts.convergence()}.is_met(diff, z.zeroth_derivative() - opts.target);
+            if settled && tolerance < spacing && diff.abs() <= spacing && residual_met {
+                log::warn!("Tolerance of {} cannot be met at the precision of {}, stopping at the root", tolerance, next);
+                return (NewtonResult{
+                    root: Some(next),
+                    iterations: count,
+                    termination: TerminationReason::ToleranceUnachievable
+                }, Some(z));
+            }
+            // A step below the tolerance here has only missed the residual threshold, which is no cycle
+            if settled && diff.abs() >= tolerance && previous.is_some_and(|previous| (next - previous).abs() < tolerance) {
+                if debug {
+                    println!("Cycling between {} and {}", current, next);
+                }
+                return (NewtonResult{
+                    root: None,
+                    iterations: count,
+                    termination: TerminationReason::Cycling
+                }, Some(z));
+            }
+            // Only the residuals after the guess are counted, so that a guess near an extremum of `f`
+            // does not set a bar that the iterates only clear once they have come back from a long
+            // step, and growing steps are left to diverge rather than counted as stalls
+            let residual = (z.zeroth_derivative() - opts.target).abs();
+            if count > 1 {
+                if residual < best_residual * stagnation_factor {
+                    best_residual = residual;
+                    stalled = 0;
+                } else if diff.abs() <= last_step {
+                    stalled += 1;
+                }
+            }
+            last_step = diff.abs();
+            if stalled >= STAGNATION_WINDOW {
+                if debug {
+                    println!("Residual stopped improving at: {}", current);
+                }
+                return (NewtonResult{
+                    root: None,
+                    iterations: count,
+                    termination: TerminationReason::Stagnated
+                }, Some(z));
+            }
+            if count > opts.patience {
+                if debug {
+                    println!("Failed to find root with initial guess of {}", opts.guess);
+                    println!("Last iteration was: {}", current);
+                    println!("Try updating the initial guess or increasing the tolerance or patience");
+                }
+                return (NewtonResult{
+                    root: None,
+                    iterations: count,
+                    termination: TerminationReason::PatienceExhausted
+                }, Some(z));
+            }
+            previous = Some(current);
+            current = next;
+            spare = Some(z);
+        }
+    }
+}
+
+/// Newton's method that spends one unit of `budget` per evaluation of `f`.
+///
+/// The solve stops once the budget reaches zero, leaving whatever remains of it for the caller,
+/// so a single evaluation budget can be shared between many solves.
+pub fn newton_budgeted<F, N, T>(f: F, guess: T, budget: &mut u32, tolerance: T) -> NewtonResult<T>
+where
+    F: Fn(N) -> N + Send + Sync,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
+    T: DualNumFloat
+{
+    let mut current = guess;
+    let mut count = 0;
+    if !(guess.is_finite() && tolerance.is_finite()) {
+        return NewtonResult{
+            root: None,
+            iterations: count,
+            termination: TerminationReason::NonFinite
+        };
+    }
+    loop {
+        if *budget == 0 {
+            return NewtonResult{
+                root: None,
+                iterations: count,
+                termination: TerminationReason::BudgetExhausted
+            };
+        }
+        let x = match from_scalar::<N, T>(current) {
+            Ok(x) => x.execute_derivative(),
+            Err(_) => return NewtonResult{
+                root: None,
+                iterations: count,
+                termination: TerminationReason::NotRepresentable
+            }
+        };
+        *budget -= 1;
+        count += 1;
+        let z = f(x);
+        if z.first_derivative() == T::zero() {
+            return NewtonResult{
+                root: None,
+                iterations: count,
+                termination: TerminationReason::ZeroDerivative
+            };
+        }
+        let next = current - z.zeroth_derivative() / z.first_derivative();
+        if !next.is_finite() {
+            return NewtonResult{
+                root: None,
+                iterations: count,
+                termination: TerminationReason::Diverged
+            };
+        }
+        if (next - current).abs() < tolerance {
+            return NewtonResult{
+                root: Some(next),
+                iterations: count,
+                termination: TerminationReason::ToleranceMet
+            };
+        }
+        current = next;
+    }
+}
+
+/// A step whose actual reduction of the residual falls below this fraction of the predicted one is rejected
+const TRUST_ACCEPT_RATIO: f64 = 0.1;
+
+/// The fraction of the predicted reduction below which the trust radius shrinks
+const TRUST_SHRINK_RATIO: f64 = 0.25;
+
+/// The fraction of the predicted reduction above which a step that reaches the radius grows it
+const TRUST_EXPAND_RATIO: f64 = 0.75;
+
+/// Newton's method with trust-region step acceptance, starting from a trust radius of `radius`.
+///
+/// Each Newton step is cut down to the trust radius and compared against the linear model of `f`:
+/// the step is taken only if it reduces `(f(x) - target)^2` by at least a fraction of what the
+/// model predicts, and the radius shrinks after poor agreement and grows after good agreement.
+/// Unlike plain Newton's method this cannot be thrown far away by a nearly flat derivative, and it
+/// stagnates rather than diverging once the radius falls below the tolerance without any progress.
+///
+/// Every evaluation of `f`, including those of rejected steps, counts as an iteration.
+pub fn newton_trust_region<F, N, T>(f: F, radius: T, opts: NewtonOptions<T>) -> NewtonResult<T>
+where
+    F: Fn(N) -> N + Send + Sync,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
+    T: DualNumFloat
+{
+    let half = T::from(0.5).unwrap();
+    let mut count = 0;
+    let result = |root: Option<T>, iterations: i32, termination: TerminationReason| NewtonResult{root, iterations, termination};
+    if !(opts.guess.is_finite() && opts.tolerance.is_finite() && radius.is_finite() && radius > T::zero()) {
+        return result(None, count, TerminationReason::NonFinite);
+    }
+    let evaluate = |x: T| from_scalar::<N, T>(x).map(|x| {
+        let z = f(x.execute_derivative());
+        (z.zeroth_derivative() - opts.target, z.first_derivative())
+    });
+    let mut current = opts.guess;
+    let mut radius = radius;
+    count += 1;
+    let Ok((mut residual, mut slope)) = evaluate(current) else {
+        return result(None, count, TerminationReason::NotRepresentable);
+    };
+    loop {
+        if slope == T::zero() {
+            return result(None, count, TerminationReason::ZeroDerivative);
+        }
+        let newton_step = -residual / slope;
+        // Close to the root the reductions are lost to rounding, so convergence is judged on the full step
+        if count > opts.min_iterations && opts.convergence().is_met(newton_step, residual) {
+            return result(Some(current + newton_step), count, TerminationReason::ToleranceMet);
+        }
+        let step = if newton_step.abs() > radius { radius.copysign(newton_step) } else { newton_step };
+        let next = current + step;
+        if !next.is_finite() {
+            return result(None, count, TerminationReason::Diverged);
+        }
+        if count > opts.patience {
+            return result(None, count, TerminationReason::PatienceExhausted);
+        }
+        count += 1;
+        let (next_residual, next_slope) = match evaluate(next) {
+            Ok(sample) if sample.0.is_finite() && sample.1.is_finite() => sample,
+            Ok(_) => (T::infinity(), T::zero()),
+            Err(_) => return result(None, count, TerminationReason::NotRepresentable)
+        };
+        let predicted = half * (residual * residual - (residual + slope * step).powi(2));
+        let actual = half * (residual * residual - next_residual * next_residual);
+        let ratio = if predicted > T::zero() { actual / predicted } else { T::zero() };
+        if ratio < T::from(TRUST_SHRINK_RATIO).unwrap() {
+            radius = step.abs() * T::from(TRUST_SHRINK_RATIO).unwrap();
+        } else if ratio > T::from(TRUST_EXPAND_RATIO).unwrap() && step.abs() >= radius {
+            radius = radius + radius;
+        }
+        if ratio > T::from(TRUST_ACCEPT_RATIO).unwrap() {
+            (current, residual, slope) = (next, next_residual, next_slope);
+        } else if radius < opts.tolerance {
+            return result(None, count, TerminationReason::Stagnated);
+        }
+    }
+}
+
+/// Safeguarded Newton's method that maintains a bracket around a sign change of `f(x) - target`.
+///
+/// Each iteration attempts a Newton step from the current iterate, but takes a bisection step of
+/// the bracket instead whenever the Newton step would leave the bracket or would not shrink it at
+/// least as fast as bisecting. The bracket is then tightened around the new iterate so the solve
+/// converges whenever the initial bracket contains a sign change.
+pub fn newton_bisection<F, N, T>(f: F, opts: HybridOptions<T>) -> NewtonResult<T>
+where
+    F: Fn(N) -> N + Send + Sync,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
+    T: DualNumFloat
+{
+    hybrid_solve(f, opts, false).0
+}
+
+/// Solves as `newton_bisection` does, also returning the last bracket known to hold the root.
+///
+/// Once the solve converges, the function is evaluated half the tolerance either side of the root,
+/// and the bracket is narrowed to those points wherever the sign changes across them. Otherwise the
+/// bracket is bisected until it is no wider than the tolerance, and the root is moved into it if it
+/// lay outside. Either way the width is a rigorous bound on the error of the root, rather than an
+/// estimate from the last step.
+pub fn newton_bisection_bracketed<F, N, T>(f: F, opts: HybridOptions<T>) -> (NewtonResult<T>, Interval<T>)
+where
+    F: Fn(N) -> N + Send + Sync,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
+    T: DualNumFloat
+{
+    hybrid_solve(f, opts, true)
+}
+
+/// The hybrid behind `newton_bisection`, which only narrows the final bracket when `certify` is set
+fn hybrid_solve<F, N, T>(f: F, opts: HybridOptions<T>, certify: bool) -> (NewtonResult<T>, Interval<T>)
+where
+    F: Fn(N) -> N + Send + Sync,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
+    T: DualNumFloat
+{
+    let two = T::from(2).unwrap();
+    let initial = Interval::new(opts.lower, opts.upper);
+    let not_representable = |iterations, bracket| (NewtonResult{
+        root: None,
+        iterations,
+        termination: TerminationReason::NotRepresentable
+    }, bracket);
+    if !(opts.lower.is_finite() && opts.upper.is_finite() && opts.tolerance.is_finite()) {
+        return (NewtonResult{
+            root: None,
+            iterations: 0,
+            termination: TerminationReason::NonFinite
+        }, initial);
+    }
+    let sample = |x: T| from_scalar::<N, T>(x).map(|x| f(x).zeroth_derivative() - opts.target);
+    let (flower, fupper) = match (sample(opts.lower), sample(opts.upper)) {
+        (Ok(flower), Ok(fupper)) => (flower, fupper),
+        _ => return not_representable(0, initial)
+    };
+    if flower == T::zero() || fupper == T::zero() {
+        let root = if flower == T::zero() { opts.lower } else { opts.upper };
+        return (NewtonResult{
+            root: Some(root),
+            iterations: 0,
+            termination: TerminationReason::ToleranceMet
+        }, Interval::new(root, root));
+    }
+    if flower.signum() == fupper.signum() {
+        return (NewtonResult{
+            root: None,
+            iterations: 0,
+            termination: TerminationReason::NotBracketed
+        }, initial);
+    }
+    // Orient the bracket so that the function is negative at `neg` and positive at `pos`
+    let (mut neg, mut pos) = if flower < T::zero() { (opts.lower, opts.upper) } else { (opts.upper, opts.lower) };
+    let bracket = |neg: T, pos: T| if neg < pos { Interval::new(neg, pos) } else { Interval::new(pos, neg) };
+    let mut current = (opts.lower + opts.upper) / two;
+    let mut last_step = (opts.upper - opts.lower).abs();
+    let mut count = 0;
+    loop {
+        count += 1;
+        let x = match from_scalar::<N, T>(current) {
+            Ok(x) => x.execute_derivative(),
+            Err(_) => return not_representable(count, bracket(neg, pos))
+        };
+        let z = f(x);
+        let value = z.zeroth_derivative() - opts.target;
+        if value == T::zero() {
+            return (NewtonResult{
+                root: Some(current),
+                iterations: count,
+                termination: TerminationReason::ToleranceMet
+            }, Interval::new(current, current));
+        }
+        if value < T::zero() {
+            neg = current;
+        } else {
+            pos = current;
+        }
+        let newton_next = current - value / z.first_derivative();
+        let Interval{lower: low, upper: high} = bracket(neg, pos);
+        let inside = newton_next.is_finite() && low < newton_next && newton_next < high;
+        let next = if inside && (two * value).abs() <= (last_step * z.first_derivative()).abs() {
+            newton_next
+        } else {
+            (neg + pos) / two
+        };
+        last_step = (next - current).abs();
+        if last_step < opts.tolerance {
+            let converged = |root| NewtonResult{
+                root: Some(root),
+                iterations: count,
+                termination: TerminationReason::ToleranceMet
+            };
+            if !certify {
+                return (converged(next), Interval::new(low, high));
+            }
+            let a = (next - opts.tolerance / two).max(low);
+            let b = (next + opts.tolerance / two).min(high);
+            if let (Ok(fa), Ok(fb)) = (sample(a), sample(b)) {
+                if fa.signum() != fb.signum() || fa == T::zero() || fb == T::zero() {
+                    return (converged(next), Interval::new(a, b));
+                }
+            }
+            while (pos - neg).abs() > opts.tolerance {
+                let mid = (neg + pos) / two;
+                match sample(mid) {
+                    Ok(fm) if fm == T::zero() => return (converged(mid), Interval::new(mid, mid)),
+                    Ok(fm) if fm < T::zero() => neg = mid,
+                    Ok(_) => pos = mid,
+                    Err(_) => break
+                }
+            }
+            let narrowed = bracket(neg, pos);
+            let root = if narrowed.contains(next) { next } else { narrowed.midpoint() };
+            return (converged(root), narrowed);
+        }
+        if count > opts.patience {
+            return (NewtonResult{
+                root: None,
+                iterations: count,
+                termination: TerminationReason::PatienceExhausted
+            }, Interval::new(low, high));
+        }
+        current = next;
+    }
+}
+
+/// Caps the resolution of a grid over `[lower, upper]` so that consecutive samples stay distinct.
+///
+/// The samples cannot be closer together than the spacing of representable floats at the largest
+/// bound, and the sample indices themselves must be exactly representable in `T`.
+fn effective_resolution<T>(lower: T, upper: T, resolution: i32) -> i32 where T: DualNumFloat {
+    let spacing = (T::epsilon() * lower.abs().max(upper.abs())).max(T::min_positive_value());
+    let distinguishable = ((upper - lower) / spacing).min(T::one() / T::epsilon()).floor();
+    match distinguishable.to_i32() {
+        Some(cap) if cap < resolution => {
+            log::warn!("Resolution of {} cannot be represented over [{}, {}], using {} instead", resolution, lower, upper, cap.max(1));
+            cap.max(1)
+        },
+        _ => resolution
+    }
+}
+
+/// The number of times a bracket is halved while telling a pole apart from a root
+const POLE_HALVINGS: i32 = 4;
+
+/// Whether the sign change across `bracket` is a pole of `f` rather than a root.
+///
+/// The bracket is halved towards the sign change a few times. Near a root `|f - target|` shrinks
+/// with the bracket, so the largest value at its ends falls, while near a pole it only grows.
+fn is_pole<F, N, T>(f: &F, bracket: &Interval<T>, target: T) -> bool
+where
+    F: Fn(N) -> N,
+    N: Derivable<T> + Coerceable<T>,
+    T: DualNumFloat
+{
+    let sample = |x: T| from_scalar::<N, T>(x).ok().map(|x| f(x).zeroth_derivative() - target);
+    let (mut a, mut b) = (bracket.lower, bracket.upper);
+    let (mut fa, mut fb) = match (sample(a), sample(b)) {
+        (Some(fa), Some(fb)) if fa != T::zero() && fb != T::zero() => (fa, fb),
+        _ => return false
+    };
+    let initial = fa.abs().max(fb.abs());
+    for _ in 0..POLE_HALVINGS {
+        let mid = (a + b) / T::from(2).unwrap();
+        let fm = match sample(mid) {
+            Some(fm) if fm != T::zero() => fm,
+            _ => return false
+        };
+        if (fm > T::zero()) == (fa > T::zero()) {
+            (a, fa) = (mid, fm);
+        } else {
+            (b, fb) = (mid, fm);
+        }
+    }
+    fa.abs().max(fb.abs()) >= initial
+}
+
+/// Finds the brackets of `find_bisections` whose sign change is a pole of `f`, such as those of
+/// `tan`, rather than a root. `root_search` reports these in `RootSearchResult::poles` instead of
+/// solving them.
+pub fn scan_for_poles<F, N, T>(f: F, opts: BisectionOptions<T>) -> Vec<Interval<T>>
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    // A root that `f` only touches has no sign change to test for a pole
+    let target = opts.target;
+    find_brackets_in_domain(&f, |_| true, opts).brackets.into_iter()
+        .filter(|(bisection, crossing)| *crossing != Crossing::Touching && is_pole(&f, bisection, target))
+        .map(|(bisection, _)| bisection)
+        .collect()
+}
+
+pub fn find_bisections<F, N, T>(f: F, opts: BisectionOptions<T>) -> Vec<Interval<T>>
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    find_brackets_in_domain(f, |_| true, opts).brackets.into_iter().map(|(interval, _)| interval).collect()
+}
+
+/// Scans the grid of `opts` as `find_bisections` does, returning the sample `(x, f(x))` at which
+/// `|f(x) - target|` is smallest, or `None` if no sample could be evaluated.
+///
+/// Where the grid holds no sign change this is still the best guess to start Newton's method
+/// from, as it may lie close to a root at which `f` only touches the target, or to a near miss.
+pub fn grid_scan<F, N, T>(f: F, opts: BisectionOptions<T>) -> Option<(T, T)>
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    let target = opts.target;
+    find_brackets_in_domain(f, |_| true, opts).samples.into_iter()
+        .min_by(|a, b| (a.1 - target).abs().partial_cmp(&(b.1 - target).abs()).unwrap_or(std::cmp::Ordering::Equal))
+}
+
+/// Splits the range of `opts` into `regions` equal parts and counts the brackets that
+/// `find_bisections` finds in each of them, as a coarse map of where the roots of `f` lie.
+///
+/// A bracket is counted in the region holding its midpoint. Dense regions can then be searched
+/// again at a finer resolution. Panics unless `regions` is positive.
+pub fn root_density<F, N, T>(f: F, regions: i32, opts: BisectionOptions<T>) -> Vec<(Interval<T>, usize)>
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    if regions < 1 {
+        panic!("A density map needs at least one region, not {}", regions)
+    }
+    let (lower, upper) = (opts.lower, opts.upper);
+    let edge = |i: i32| if i == regions { upper } else { lower + (upper - lower) * T::from(i).unwrap() / T::from(regions).unwrap() };
+    let mut counts = vec![0; regions as usize];
+    for bracket in find_bisections(f, opts) {
+        let region = ((bracket.midpoint() - lower) / (upper - lower) * T::from(regions).unwrap()).to_usize().unwrap_or(0);
+        counts[region.min(regions as usize - 1)] += 1;
+    }
+    counts.into_iter().enumerate().map(|(i, count)| (Interval::new(edge(i as i32), edge(i as i32 + 1)), count)).collect()
+}
+
+/// Finds the brackets of the roots of `f` between consecutive points of an explicit grid, for
+/// sampling that a uniform `resolution` cannot express, such as non-uniform measurement locations.
+///
+/// The points must be sorted in ascending order. Points that cannot be represented by the dual
+/// number type are skipped, and a point where `f` is exactly zero is bracketed by its neighbours.
+pub fn find_bisections_on<F, N, T>(f: F, points: &[T]) -> Vec<Interval<T>>
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    if points.windows(2).any(|pair| pair[0] >= pair[1]) {
+        panic!("Grid points must be sorted in strictly ascending order")
+    }
+    let samples: Vec<(T, T)> = points.iter()
+        .filter_map(|x| from_scalar::<N, T>(*x).ok().map(|n| (*x, f(n).zeroth_derivative())))
+        .collect();
+    bracket::sign_changes(&samples).into_iter().map(|(bracket, _)| bracket).collect()
+}
+
+/// The brackets found by scanning the grid along with the smallest and largest sample seen
+pub(crate) struct GridScan<T> where T: DualNumFloat {
+    pub(crate) brackets: Vec<(Interval<T>, Crossing)>,
+    pub(crate) range: Option<(T, T)>,
+    /// Every grid point that was evaluated along with the value of `f` there
+    pub(crate) samples: Vec<(T, T)>,
+    /// The slope of `f` at each of the samples
+    pub(crate) slopes: Vec<T>
+}
+
+/// Scans the grid for brackets, keeping the direction of the sign change found for each of them
+pub(crate) fn find_brackets_in_domain<F, D, N, T>(f: F, domain: D, opts: BisectionOptions<T>) -> GridScan<T>
+where
+    F: Fn(N) -> N + Sync + Send,
+    D: Fn(T) -> bool,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
+    T: DualNumFloat
+{
+    let resolution = effective_resolution(opts.lower, opts.upper, opts.resolution);
+    // Each point is computed from its index alone, so that the grid spans exactly `[lower, upper]`
+    let point = |i: i32| if i >= resolution {
+        opts.upper
+    } else {
+        opts.lower + (opts.upper - opts.lower) * T::from(i).unwrap() / T::from(resolution).unwrap()
+    };
+    // Each sample is the value of `f(x) - target` along with its slope and the raw value of `f(x)`.
+    // A sample within half the spacing of the floats around it from a root is taken to be exactly
+    // zero, as the root rounds to the sample itself and could never lie strictly inside of a
+    // bracket ending there. Only the sign detection sees the snapped value, not `samples`.
+    let sample = |x: T| from_scalar::<N, T>(x).ok().map(|n| {
+        let z = f(n.execute_derivative());
+        let value = z.zeroth_derivative() - opts.target;
+        let on_root = (value / z.first_derivative()).abs() <= T::epsilon() * x.abs() / T::from(2).unwrap();
+        (if value.abs() <= opts.sign_epsilon || on_root { T::zero() } else { value }, z.first_derivative(), z.zeroth_derivative())
+    });
+
+    // Where `f - target` turns back away from zero between two samples of the same sign, it may
+    // dip across zero and back between them. Narrow in on the turning point by bisecting on the
+    // side the slope leads towards zero, until a sample changes sign.
+    let dip = |(a, fa, da): (T, T, T), (b, fb, db): (T, T, T)| {
+        let crossing = if fa < fb { Crossing::Rising } else { Crossing::Falling };
+        let mut brackets = Vec::new();
+        if fa * da >= T::zero() || fb * db <= T::zero() {
+            return brackets;
+        }
+        let (mut low, mut high) = (a, b);
+        for _ in 0..opts.subdivisions {
+            let mid = (low + high) / T::from(2).unwrap();
+            if !domain(mid) {
+                break;
+            }
+            let (fm, dm) = match sample(mid) {
+                Some((fm, dm, _)) => (fm, dm),
+                None => break
+            };
+            if fm == T::zero() {
+                brackets.push((Interval::new(low, high), crossing));
+                break;
+            }
+            if (fm > T::zero()) != (fa > T::zero()) {
+                let (into, out) = if fa > T::zero() { (Crossing::Falling, Crossing::Rising) } else { (Crossing::Rising, Crossing::Falling) };
+                brackets.push((Interval::new(low, mid), into));
+                brackets.push((Interval::new(mid, high), out));
+                break;
+            }
+            if fm * dm < T::zero() {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        brackets
+    };
+    let mut values: Vec<(Interval<T>, Crossing)> = Vec::new();
+    let mut range: Option<(T, T)> = None;
+    let mut samples: Vec<(T, T)> = Vec::new();
+    let mut slopes: Vec<T> = Vec::new();
+    // The grid points since the last one outside of the domain, or that could not be represented
+    // by the dual number type, as `(x, f(x) - target, f'(x))`. Each such run is bracketed alone.
+    let mut run: Vec<(T, T, T)> = Vec::new();
+    // A last missing point ends the final run
+    for x in (0..=resolution).map(|i| Some(point(i))).chain([None]) {
+        let sampled = match x {
+            Some(x) if domain(x) => sample(x).map(|sampled| (x, sampled)),
+            _ => None
+        };
+        if let Some((x, (value, slope, raw))) = sampled {
+            samples.push((x, raw));
+            slopes.push(slope);
+            run.push((x, value, slope));
+            continue;
+        }
+        if run.len() >= 2 {
+            for (_, value, _) in &run {
+                range = Some(range.map_or((*value, *value), |(min, max): (T, T)| (min.min(*value), max.max(*value))));
+            }
+            let signs: Vec<(T, T)> = run.iter().map(|(x, value, _)| (*x, *value)).collect();
+            values.extend(bracket::sign_changes_with(&signs, |i| dip(run[i], run[i + 1])));
+        }
+        run.clear();
+    }
+    GridScan{brackets: values, range, samples, slopes}
+}
+
+pub fn root_search<F, N, T>(f: F, opts: RootSearchOptions<T>) -> RootSearchResult<T>
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    root_search_in_domain(f, |_| true, opts)
+}
+
+/// Searches for roots of a function implemented by `evaluator` as in `root_search`
+pub fn root_search_evaluate<E, N, T>(evaluator: &E, opts: RootSearchOptions<T>) -> RootSearchResult<T>
+where
+    E: Evaluate<T> + Sync,
+    N: DualNum<T> + Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div + Send + Sync,
+    T: DualNumFloat
+{
+    root_search(|x: N| evaluator.eval(x), opts)
+}
+
+/// A function boxed as a trait object, so that functions built at runtime, such as from parsed
+/// expressions, can be stored together and solved with `root_search_boxed`
+pub type BoxedFunction<N> = Box<dyn Fn(N) -> N + Send + Sync>;
+
+/// Searches for roots of a boxed function as in `root_search`.
+///
+/// Every boxed function shares a single instantiation of the search for each `N`, at the cost of a
+/// dynamic call per evaluation.
+pub fn root_search_boxed<N, T>(f: &BoxedFunction<N>, opts: RootSearchOptions<T>) -> RootSearchResult<T>
+where
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    root_search(f, opts)
+}
+
+/// The roots of a periodic function over a single period, from which the roots over any range follow
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeriodicRoots<T> where T: DualNumFloat {
+    /// The roots within `[0, period)`, in ascending order
+    pub roots: Vec<T>,
+    pub crossings: Vec<Crossing>,
+    pub period: T
+}
+
+/// The most periods that `PeriodicRoots::within` enumerates the roots of
+const MAX_PERIODS: i64 = 1_000_000;
+
+impl <T: DualNumFloat> PeriodicRoots<T> {
+    /// Every root within `[lower, upper]`, made by adding integer multiples of the period to `roots`.
+    ///
+    /// Fails for bounds that are not finite or reversed, and for a range spanning more than a
+    /// million periods, or periods whose index does not fit in an `i64`.
+    pub fn within(&self, lower: T, upper: T) -> Result<Vec<T>, RootSearchError> {
+        if !(lower.is_finite() && upper.is_finite()) {
+            return Err(RootSearchError::NonFinite);
+        }
+        if lower > upper {
+            return Err(RootSearchError::BoundsReversed);
+        }
+        let first = (lower / self.period).floor().to_i64().ok_or(RootSearchError::TooManyPeriods)?;
+        let last = (upper / self.period).floor().to_i64().ok_or(RootSearchError::TooManyPeriods)?;
+        if last.checked_sub(first).is_none_or(|periods| periods >= MAX_PERIODS) {
+            return Err(RootSearchError::TooManyPeriods);
+        }
+        Ok((first..=last)
+            .flat_map(|k| self.roots.iter().map(move |root| *root + T::from(k).unwrap() * self.period))
+            .filter(|root| lower <= *root && *root <= upper)
+            .collect())
+    }
+}
+
+/// Searches for the roots of a function with the given period over `[0, period)` alone, as the
+/// roots anywhere else are those same roots shifted by whole periods.
+///
+/// The bounds and symmetry hint of `opts` are ignored, and the full `resolution` is spent on the
+/// single period.
+pub fn periodic_root_search<F, N, T>(f: F, period: T, opts: RootSearchOptions<T>) -> PeriodicRoots<T>
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    if period <= T::zero() || !period.is_finite() {
+        panic!("Period must be positive and finite")
+    }
+    // The bounds are always checked, as a root on the start of the period would otherwise be missed
+    let res = root_search(f, RootSearchOptions{
+        lower: T::zero(),
+        upper: period,
+        symmetry: Symmetry::None,
+        boundary_roots: true,
+        ..opts
+    });
+    let (roots, crossings) = res.roots.into_iter().zip(res.crossings)
+        .filter(|(root, _)| *root < period - opts.tolerance)
+        .unzip();
+    PeriodicRoots{roots, crossings, period}
+}
+
+/// Searches for roots of a function that is only defined where `domain` returns `true`.
+///
+/// Grid intervals with an endpoint outside of the domain are skipped and every Newton solve
+/// is restricted to the domain as in `newton_in_domain`.
+pub fn root_search_in_domain<F, D, N, T>(f: F, domain: D, opts: RootSearchOptions<T>) -> RootSearchResult<T>
+where
+    F: Fn(N) -> N + Sync + Send,
+    D: Fn(T) -> bool + Sync,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    match try_root_search_in_domain(f, domain, opts) {
+        Ok(res) => res,
+        Err(RootSearchError::FlatFunction) => RootSearchResult::empty(),
+        Err(error) => panic!("{}", error)
+    }
+}
+
+/// Searches for roots as `root_search` does, but reports invalid bounds and flat functions as errors
+pub fn try_root_search<F, N, T>(f: F, opts: RootSearchOptions<T>) -> Result<RootSearchResult<T>, RootSearchError>
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    try_root_search_in_domain(f, |_| true, opts)
+}
+
+/// Searches for roots as `root_search_in_domain` does, but reports invalid bounds and flat functions as errors
+pub fn try_root_search_in_domain<F, D, N, T>(f: F, domain: D, opts: RootSearchOptions<T>) -> Result<RootSearchResult<T>, RootSearchError>
+where
+    F: Fn(N) -> N + Sync + Send,
+    D: Fn(T) -> bool + Sync,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    try_root_search_guessed(f, domain, &even_guesses, opts, &InOrder)
+}
+
+/// Searches for roots as `root_search` does, starting the Newton solves of each bracket from the
+/// guesses that `guesses` returns for it instead of from evenly spaced ones.
+///
+/// The guesses are tried in order until one converges to a root inside of the bracket.
+pub fn root_search_with_guesses<F, G, N, T>(f: F, guesses: G, opts: RootSearchOptions<T>) -> RootSearchResult<T>
+where
+    F: Fn(N) -> N + Sync + Send,
+    G: Fn(Interval<T>) -> Vec<T> + Sync,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    match try_root_search_guessed(f, |_| true, &guesses, opts, &InOrder) {
+        Ok(res) => res,
+        Err(RootSearchError::FlatFunction) => RootSearchResult::empty(),
+        Err(error) => panic!("{}", error)
+    }
+}
+
+/// Searches for roots as `try_root_search_in_domain` does, solving the brackets with `solver`
+pub(crate) fn try_root_search_guessed<F, D, G, S, N, T>(f: F, domain: D, guesses: &G, mut opts: RootSearchOptions<T>, solver: &S) -> Result<RootSearchResult<T>, RootSearchError>
+where
+    F: Fn(N) -> N + Sync + Send,
+    D: Fn(T) -> bool + Sync,
+    G: Fn(Interval<T>) -> Vec<T> + Sync,
+    S: SolveBrackets<T>,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    validate_bounds(&mut opts)?;
+    // An odd function is only symmetric about a target of zero
+    let symmetric = match opts.symmetry {
+        Symmetry::None => false,
+        Symmetry::Even => true,
+        Symmetry::Odd => opts.target == T::zero()
+    };
+    let mut result = if symmetric && opts.lower < T::zero() && T::zero() < opts.upper {
+        mirrored_root_search(&f, &domain, guesses, opts, solver)?
+    } else {
+        search_brackets(&f, &domain, guesses, opts, solver)?
+    };
+    add_boundary_roots(&f, &domain, &mut result, &opts);
+    Ok(result)
+}
+
+/// Adds the bounds of `opts` to the roots of `result` when `opts.boundary_roots` is set and the
+/// function is within the tolerance of the target at them, then finishes the roots as in `finish_roots`
+pub(crate) fn add_boundary_roots<F, D, N, T>(f: &F, domain: &D, result: &mut RootSearchResult<T>, opts: &RootSearchOptions<T>)
+where
+    F: Fn(N) -> N,
+    D: Fn(T) -> bool,
+    N: Derivable<T> + Coerceable<T>,
+    T: DualNumFloat
+{
+    for bound in [opts.lower, opts.upper].into_iter().filter(|_| opts.boundary_roots) {
+        if !domain(bound) || result.roots.iter().any(|root| (*root - bound).abs() <= opts.tolerance) {
+            continue;
+        }
+        let z = match from_scalar::<N, T>(bound) {
+            Ok(x) => f(x.execute_derivative()),
+            Err(_) => continue
+        };
+        if (z.zeroth_derivative() - opts.target).abs() > opts.tolerance {
+            continue;
+        }
+        let crossing = if z.first_derivative() < T::zero() { Crossing::Falling } else { Crossing::Rising };
+        if opts.direction.is_some_and(|direction| direction != crossing) {
+            continue;
+        }
+        if bound == opts.lower {
+            result.roots.insert(0, bound);
+            result.crossings.insert(0, crossing);
+        } else {
+            result.roots.push(bound);
+            result.crossings.push(crossing);
+        }
+    }
+    finish_roots(result, opts);
+}
+
+/// Keeps only the lowest `opts.max_total_roots` of the roots of `result`, rounded to `opts.round_to_decimals`
+pub(crate) fn finish_roots<T>(result: &mut RootSearchResult<T>, opts: &RootSearchOptions<T>) where T: DualNumFloat {
+    if let Some(max) = opts.max_total_roots {
+        result.roots.truncate(max);
+        result.crossings.truncate(max);
+    }
+    result.roots.iter_mut().for_each(|root| *root = opts.rounded(*root));
+}
+
+/// Rounds `value` to the nearest multiple of `10^-decimals`, leaving it as it is where scaling it
+/// up would overflow
+pub fn round_to_decimals<T>(value: T, decimals: i32) -> T where T: DualNumFloat {
+    let scale = T::from(10).unwrap().powi(decimals);
+    let scaled = value * scale;
+    if scaled.is_finite() { scaled.round() / scale } else { value }
+}
+
+/// Scans the grid for brackets and solves each of them, once the bounds have been validated
+pub(crate) fn search_brackets<F, D, G, S, N, T>(f: F, domain: &D, guesses: &G, opts: RootSearchOptions<T>, solver: &S) -> Result<RootSearchResult<T>, RootSearchError>
+where
+    F: Fn(N) -> N + Sync + Send,
+    D: Fn(T) -> bool + Sync,
+    G: Fn(Interval<T>) -> Vec<T> + Sync,
+    S: SolveBrackets<T>,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    let scan = find_brackets_in_domain(&f, domain, opts.bisection_options());
+    solve_scan(f, domain, guesses, scan, &opts, solver)
+}
+
+/// Solves the brackets of a grid scan into the result of a search, reporting a function that is
+/// flat over the scan as an error
+pub(crate) fn solve_scan<F, D, G, S, N, T>(f: F, domain: &D, guesses: &G, scan: GridScan<T>, opts: &RootSearchOptions<T>, solver: &S) -> Result<RootSearchResult<T>, RootSearchError>
+where
+    F: Fn(N) -> N + Sync + Send,
+    D: Fn(T) -> bool + Sync,
+    G: Fn(Interval<T>) -> Vec<T> + Sync,
+    S: SolveBrackets<T>,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
+    T: DualNumFloat
+{
+    if let Some((min, max)) = scan.range {
+        if max - min <= opts.tolerance {
+            return Err(RootSearchError::FlatFunction);
+        }
+    }
+    let mut result = solve_brackets_with(f, domain, guesses, scan.brackets, opts, solver);
+    if let Some(tolerance) = opts.plateau_tolerance {
+        result.plateaus = plateaus(&scan.samples, &result.roots, tolerance, opts.target);
+    }
+    Ok(result)
+}
+
+/// The midpoints of the runs of consecutive samples over which `|f - target|` stays below
+/// `tolerance`, leaving out the runs that hold one of `roots`
+pub(crate) fn plateaus<T>(samples: &[(T, T)], roots: &[T], tolerance: T, target: T) -> Vec<T> where T: DualNumFloat {
+    let mut plateaus = Vec::new();
+    let mut run: Option<(T, T)> = None;
+    for (x, fx) in samples.iter().map(|(x, fx)| (*x, *fx - target)).chain([(T::nan(), T::infinity())]) {
+        if fx.abs() < tolerance {
+            run = Some(run.map_or((x, x), |(start, _)| (start, x)));
+            continue;
+        }
+        if let Some((start, end)) = run.take() {
+            if start < end && !roots.iter().any(|root| start <= *root && *root <= end) {
+                plateaus.push((start + end) / T::from(2).unwrap());
+            }
+        }
+    }
+    plateaus
+}
+
+/// Solves every bracket with a sign change in the requested direction, as `root_search` does
+pub(crate) fn solve_brackets<F, D, G, N, T>(f: F, domain: &D, guesses: &G, brackets: Vec<(Interval<T>, Crossing)>, opts: &RootSearchOptions<T>) -> RootSearchResult<T>
+where
+    F: Fn(N) -> N + Sync + Send,
+    D: Fn(T) -> bool + Sync,
+    G: Fn(Interval<T>) -> Vec<T> + Sync,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
+    T: DualNumFloat
+{
+    solve_brackets_with(f, domain, guesses, brackets, opts, &InOrder)
+}
+
+/// The outcome of solving a single bracket of a search
+pub(crate) struct Solved<T> where T: DualNumFloat {
+    pub(crate) bracket: Interval<T>,
+    pub(crate) outcome: Result<(T, NewtonResult<T>), RootError<T>>,
+    /// The roots of the bracket besides the first, for `RootSearchOptions::exhaust_brackets`
+    pub(crate) others: Vec<(T, Crossing)>
+}
+
+/// How a search maps its brackets to their outcomes
+pub(crate) trait SolveBrackets<T> where T: DualNumFloat {
+    /// Solves the brackets below `count` with `solve`, returning the outcomes in the order of the
+    /// brackets. The brackets after the first outcome for which `enough` holds may be left unsolved.
+    fn solve_brackets<W>(&self, count: usize, solve: W, enough: &mut dyn FnMut(&Solved<T>) -> bool) -> Vec<Solved<T>>
+    where
+        W: Fn(usize) -> Solved<T> + Sync;
+}
+
+/// Solves the brackets one after another, stopping as soon as the search has enough of them
+pub(crate) struct InOrder;
+
+impl <T: DualNumFloat> SolveBrackets<T> for InOrder {
+    fn solve_brackets<W>(&self, count: usize, solve: W, enough: &mut dyn FnMut(&Solved<T>) -> bool) -> Vec<Solved<T>>
+    where
+        W: Fn(usize) -> Solved<T> + Sync
+    {
+        solve_in_order(count, solve, enough)
+    }
+}
+
+/// Solves the brackets below `count` in order until `enough` holds for one of the outcomes
+pub(crate) fn solve_in_order<W, T>(count: usize, solve: W, enough: &mut dyn FnMut(&Solved<T>) -> bool) -> Vec<Solved<T>>
+where
+    W: Fn(usize) -> Solved<T>,
+    T: DualNumFloat
+{
+    let mut solved = Vec::new();
+    for i in 0..count {
+        solved.push(solve(i));
+        if enough(solved.last().unwrap()) {
+            break;
+        }
+    }
+    solved
+}
+
+/// Solves the brackets as `solve_brackets` does, mapping them to their outcomes with `solver`
+pub(crate) fn solve_brackets_with<F, D, G, S, N, T>(f: F, domain: &D, guesses: &G, brackets: Vec<(Interval<T>, Crossing)>, opts: &RootSearchOptions<T>, solver: &S) -> RootSearchResult<T>
+where
+    F: Fn(N) -> N + Sync + Send,
+    D: Fn(T) -> bool + Sync,
+    G: Fn(Interval<T>) -> Vec<T> + Sync,
+    S: SolveBrackets<T>,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
+    T: DualNumFloat
+{
+    let (poles, brackets) = split_poles(&f, brackets, opts);
+    // The search has enough once it has found `max_total_roots`, or once a bracket has failed under `OnFailure::Abort`
+    let enough = || {
+        let mut found = 0;
+        move |solved: &Solved<T>| {
+            found += usize::from(solved.outcome.is_ok());
+            // Only the roots that `merge_other_roots` keeps count towards the limit
+            found += solved.others.iter().filter(|(_, crossing)| opts.direction.is_none_or(|direction| direction == *crossing)).count();
+            opts.max_total_roots.is_some_and(|max| found >= max) || (solved.outcome.is_err() && opts.on_failure == OnFailure::Abort)
+        }
+    };
+    let mut solved = solver.solve_brackets(brackets.len(), |i| {
+        let (bisection, _) = brackets[i];
+        let outcome = solve_bracket(&f, domain, guesses, &bisection, opts);
+        let others = match (&outcome, opts.exhaust_brackets) {
+            (Ok((root, _)), true) => other_roots_in_bracket(&f, domain, guesses, &bisection, *root, opts),
+            _ => Vec::new()
+        };
+        Solved{bracket: bisection, outcome, others}
+    }, &mut enough());
+    // A solver that solves every bracket is cut back to those that the search would have solved in order
+    let mut stop = enough();
+    let used = solved.iter().position(&mut stop).map_or(solved.len(), |i| i + 1);
+    solved.truncate(used);
+    let (outcomes, others): (Vec<_>, Vec<_>) = solved.into_iter()
+        .map(|solved| (solved.outcome.map(|(root, _)| root), solved.others))
+        .unzip();
+    let mut result = collect_roots(poles, brackets, outcomes, opts);
+    merge_other_roots(&mut result, others.into_iter().flatten().collect(), opts);
+    result
+}
+
+/// The roots of a bracket besides `root`, for `RootSearchOptions::exhaust_brackets`, found by
+/// solving the parts of the bracket on either side of every root found in it in turn
+pub(crate) fn other_roots_in_bracket<F, D, G, N, T>(f: &F, domain: &D, guesses: &G, bisection: &Interval<T>, root: T, opts: &RootSearchOptions<T>) -> Vec<(T, Crossing)>
+where
+    F: Fn(N) -> N + Sync + Send,
+    D: Fn(T) -> bool,
+    G: Fn(Interval<T>) -> Vec<T>,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
+    T: DualNumFloat
+{
+    let around = |part: Interval<T>, root: T| [
+        Interval::new(part.lower, root - opts.tolerance),
+        Interval::new(root + opts.tolerance, part.upper)
+    ];
+    let mut found = Vec::new();
+    let mut parts = around(*bisection, root).to_vec();
+    while let Some(part) = parts.pop() {
+        if part.width() <= opts.tolerance {
+            continue;
+        }
+        if let Ok((root, _)) = solve_bracket(f, domain, guesses, &part, opts) {
+            // Newton's method has converged, so the slope at the root gives the direction of the crossing
+            let rising = from_scalar::<N, T>(root).is_ok_and(|x| f(x.execute_derivative()).first_derivative() > T::zero());
+            found.push((root, if rising { Crossing::Rising } else { Crossing::Falling }));
+            parts.extend(around(part, root));
+        }
+    }
+    found
+}
+
+/// Adds the roots found by `other_roots_in_bracket` to a result, keeping the roots in ascending
+/// order and within the direction and cap of `opts`
+pub(crate) fn merge_other_roots<T>(result: &mut RootSearchResult<T>, others: Vec<(T, Crossing)>, opts: &RootSearchOptions<T>) where T: DualNumFloat {
+    if others.is_empty() {
+        return;
+    }
+    let others = others.into_iter().filter(|(_, crossing)| opts.direction.is_none_or(|direction| direction == *crossing));
+    let mut found: Vec<_> = result.roots.drain(..).zip(result.crossings.drain(..)).chain(others).collect();
+    found.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    if let Some(max) = opts.max_total_roots {
+        found.truncate(max);
+    }
+    (result.roots, result.crossings) = found.into_iter().unzip();
+}
+
+/// Brackets along with the direction of the sign change across each of them
+pub(crate) type Brackets<T> = Vec<(Interval<T>, Crossing)>;
+
+/// Separates the poles from the brackets with a sign change in the requested direction, returning
+/// them along with the brackets left to solve in ascending order
+pub(crate) fn split_poles<F, N, T>(f: &F, brackets: Brackets<T>, opts: &RootSearchOptions<T>) -> (Vec<Interval<T>>, Brackets<T>)
+where
+    F: Fn(N) -> N,
+    N: Derivable<T> + Coerceable<T>,
+    T: DualNumFloat
+{
+    let (poles, mut brackets): (Vec<_>, Vec<_>) = brackets.into_iter()
+        .filter(|(_, crossing)| opts.direction.is_none_or(|direction| direction == *crossing))
+        .partition(|(bisection, crossing)| *crossing != Crossing::Touching && is_pole(f, bisection, opts.target));
+    // Solving in ascending order makes the roots kept by `max_total_roots` the lowest ones
+    brackets.sort_by(|a, b| a.0.lower.partial_cmp(&b.0.lower).unwrap());
+    (poles.into_iter().map(|(interval, _)| interval).collect(), brackets)
+}
+
+/// Gathers the outcomes of solving the leading brackets of `brackets`, in the same order, into the
+/// result of a search
+pub(crate) fn collect_roots<T>(poles: Vec<Interval<T>>, brackets: Brackets<T>, outcomes: Vec<Result<T, RootError<T>>>, opts: &RootSearchOptions<T>) -> RootSearchResult<T> where T: DualNumFloat {
+    let mut roots: Vec<T> = Vec::new();
+    let mut crossings: Vec<Crossing> = Vec::new();
+    let mut outside_bracket_roots: Vec<T> = Vec::new();
+    for ((_, crossing), outcome) in brackets.iter().zip(outcomes) {
+        let outcome_failed = outcome.is_err();
+        match outcome {
+            Ok(root) => {
+                roots.push(root);
+                crossings.push(*crossing);
+            },
+            Err(RootError::OutsideBracket(root)) if opts.keep_outside_bracket => outside_bracket_roots.push(root),
+            Err(_) => {}
+        }
+        if outcome_failed && opts.on_failure == OnFailure::Abort {
+            break;
+        }
+    }
+    let outside_bracket_roots = dedup_roots(outside_bracket_roots, opts.tolerance).into_iter()
+        .filter(|stray| roots.iter().all(|root| (*root - *stray).abs() > opts.tolerance))
+        .collect();
+    let bisections = brackets.into_iter().map(|(interval, _)| interval).collect();
+    RootSearchResult{roots, bisections, crossings, outside_bracket_roots, poles, plateaus: Vec::new()}
+}
+
+/// Searches the non-negative half of the bounds of a symmetric function and reflects the roots
+/// found there, so that only half of the range has to be scanned.
+fn mirrored_root_search<F, D, G, S, N, T>(f: F, domain: &D, guesses: &G, opts: RootSearchOptions<T>, solver: &S) -> Result<RootSearchResult<T>, RootSearchError>
+where
+    F: Fn(N) -> N + Sync + Send,
+    D: Fn(T) -> bool + Sync,
+    G: Fn(Interval<T>) -> Vec<T> + Sync,
+    S: SolveBrackets<T>,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    let upper = opts.upper.max(-opts.lower);
+    // Keep the spacing of the grid the same as that of a scan over the full bounds
+    let resolution = (T::from(opts.resolution).unwrap() * upper / (opts.upper - opts.lower)).ceil().to_i32().unwrap().max(1);
+    let odd = opts.symmetry == Symmetry::Odd;
+    // The root of an odd function at zero is already known, so the scan starts just past it
+    let half = search_brackets(&f, domain, guesses, RootSearchOptions{
+        lower: if odd { opts.tolerance } else { T::zero() },
+        upper,
+        resolution,
+        direction: None,
+        symmetry: Symmetry::None,
+        max_total_roots: None,
+        ..opts
+    }, solver)?;
+    let mirror = |crossing: Crossing| match (odd, crossing) {
+        (true, crossing) => crossing,
+        (false, Crossing::Rising) => Crossing::Falling,
+        (false, Crossing::Falling) => Crossing::Rising,
+        (_, Crossing::Touching) => Crossing::Touching
+    };
+    let keep = |root: T| opts.lower <= root && root <= opts.upper && domain(root);
+    let mut found: Vec<(T, Crossing)> = Vec::new();
+    if odd && domain(T::zero()) {
+        let step = upper / T::from(resolution).unwrap();
+        let rising = from_scalar::<N, T>(step).is_ok_and(|x| f(x).zeroth_derivative() > T::zero());
+        found.push((T::zero(), if rising { Crossing::Rising } else { Crossing::Falling }));
+    }
+    for (root, crossing) in half.roots.iter().zip(&half.crossings) {
+        if *root <= opts.tolerance {
+            continue;
+        }
+        found.push((*root, *crossing));
+        found.push((-*root, mirror(*crossing)));
+    }
+    found.retain(|(root, crossing)| keep(*root) && opts.direction.is_none_or(|direction| direction == *crossing));
+    found.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    let mut bisections: Vec<Interval<T>> = Vec::new();
+    for bisection in half.bisections {
+        bisections.push(Interval::new(-bisection.upper, -bisection.lower));
+        bisections.push(bisection);
+    }
+    bisections.retain(|bisection| opts.lower <= bisection.lower && bisection.upper <= opts.upper);
+    bisections.sort_by(|a, b| a.lower.partial_cmp(&b.lower).unwrap());
+    let mut outside_bracket_roots: Vec<T> = half.outside_bracket_roots.iter().flat_map(|root| [*root, -*root]).collect();
+    outside_bracket_roots.retain(|root| keep(*root));
+    let mut poles: Vec<Interval<T>> = half.poles.iter().flat_map(|pole| [Interval::new(-pole.upper, -pole.lower), *pole]).collect();
+    poles.retain(|pole| opts.lower <= pole.lower && pole.upper <= opts.upper);
+    poles.sort_by(|a, b| a.lower.partial_cmp(&b.lower).unwrap());
+    let mut plateaus: Vec<T> = half.plateaus.iter().flat_map(|plateau| [-*plateau, *plateau]).collect();
+    plateaus.retain(|plateau| keep(*plateau));
+    Ok(RootSearchResult{
+        roots: found.iter().map(|(root, _)| *root).collect(),
+        crossings: found.iter().map(|(_, crossing)| *crossing).collect(),
+        bisections,
+        outside_bracket_roots: dedup_roots(outside_bracket_roots, opts.tolerance),
+        poles,
+        plateaus: dedup_roots(plateaus, opts.tolerance)
+    })
+}
+
+/// Solves for a root inside of a single bracket as `root_search` does, running Newton's method from
+/// evenly spaced guesses across the bracket until one converges to a root inside of it.
+pub fn solve_bisection<F, N, T>(f: F, bisection: &Interval<T>, opts: &RootSearchOptions<T>) -> Result<T, RootError<T>>
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
+    T: DualNumFloat
+{
+    solve_bisection_in_domain(f, &|_| true, bisection, opts)
+}
+
+/// Solves a single known bracket with `newton_bisection`, using the patience, tolerance and target
+/// of `opts`, without scanning a grid for it first.
+///
+/// The hybrid converges to a root inside of the bracket whenever `f - target` changes sign across
+/// it, and reports `NotBracketed` otherwise.
+pub fn solve_with_initial_bracket<F, N, T>(f: F, bracket: &Interval<T>, opts: &RootSearchOptions<T>) -> NewtonResult<T>
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
+    T: DualNumFloat
+{
+    newton_bisection(f, HybridOptions{
+        lower: bracket.lower,
+        upper: bracket.upper,
+        patience: opts.patience,
+        tolerance: opts.tolerance,
+        target: opts.target
+    })
+}
+
+/// Tightens a bracket around a root of `f` with `steps` halvings by bisection, keeping the half
+/// across which `f` changes sign, so that Newton's method can start from much closer to the root.
+///
+/// A bracket across which `f` does not change sign is returned as it is, and one whose midpoint
+/// is exactly a root shrinks to that point.
+pub fn shrink_bracket<F, N, T>(f: F, interval: &Interval<T>, steps: i32) -> Interval<T>
+where
+    F: Fn(N) -> N,
+    N: Derivable<T> + Coerceable<T>,
+    T: DualNumFloat
+{
+    let sample = |x: T| from_scalar::<N, T>(x).ok().map(|x| f(x).zeroth_derivative());
+    let mut bracket = *interval;
+    let Some(mut flower) = sample(bracket.lower) else {
+        return bracket;
+    };
+    if !bracket.has_sign_change(&f) {
+        return bracket;
+    }
+    for _ in 0..steps {
+        let mid = bracket.midpoint();
+        let Some(fmid) = sample(mid) else {
+            break;
+        };
+        if fmid == T::zero() {
+            return Interval::new(mid, mid);
+        }
+        if (fmid > T::zero()) == (flower > T::zero()) {
+            bracket.lower = mid;
+            flower = fmid;
+        } else {
+            bracket.upper = mid;
+        }
+    }
+    bracket
+}
+
+/// Searches for roots as in `root_search`, but solves each bracket with `newton_bisection_bracketed`
+/// and returns every root along with the width of the final bracket around it, a rigorous bound on
+/// its error.
+///
+/// The symmetry hint, roots found outside of their bracket and roots on the bounds are ignored.
+pub fn roots_with_bracket_width<F, N, T>(f: F, opts: RootSearchOptions<T>) -> Vec<(T, T)>
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    let opts = validated(opts);
+    let scan = find_brackets_in_domain(&f, |_| true, opts.bisection_options());
+    let (_, brackets) = split_poles(&f, scan.brackets, &opts);
+    let mut roots = Vec::new();
+    for (bisection, _) in brackets {
+        let (res, bracket) = newton_bisection_bracketed(&f, HybridOptions{
+            lower: bisection.lower,
+            upper: bisection.upper,
+            patience: opts.patience,
+            tolerance: opts.tolerance,
+            target: opts.target
+        });
+        if let Some(root) = res.root {
+            roots.push((opts.rounded(root), bracket.upper - bracket.lower));
+        }
+        if opts.max_total_roots.is_some_and(|max| roots.len() >= max) {
+            break;
+        }
+    }
+    roots
+}
+
+fn solve_bisection_in_domain<F, D, N, T>(f: F, domain: &D, bisection: &Interval<T>, opts: &RootSearchOptions<T>) -> Result<T, RootError<T>>
+where
+    F: Fn(N) -> N + Sync + Send,
+    D: Fn(T) -> bool,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
+    T: DualNumFloat
+{
+    solve_bracket(f, domain, &even_guesses, bisection, opts).map(|(root, _)| root)
+}
+
+/// Solves a bracket as `solve_bisection` does, also returning the Newton solve that found the root
+pub(crate) fn solve_bracket<F, D, G, N, T>(f: F, domain: &D, guesses: &G, bisection: &Interval<T>, opts: &RootSearchOptions<T>) -> Result<(T, NewtonResult<T>), RootError<T>>
+where
+    F: Fn(N) -> N + Sync + Send,
+    D: Fn(T) -> bool,
+    G: Fn(Interval<T>) -> Vec<T>,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
+    T: DualNumFloat
+{
+    let inside = |root: T| if opts.inclusive_bounds {
+        bisection.contains(root)
+    } else {
+        bisection.lower < root && root < bisection.upper
+    };
+    let mut outside = None;
+    for guess in guesses(*bisection) {
+        let res = newton_in_domain(&f, domain, opts.newton_options(guess));
+        let root = match res.root {
+            Some(root) => root,
+            None => break
+        };
+        if inside(root) {
+            return Ok((root, res));
+        }
+        outside = Some(root);
+    }
+    if opts.on_failure == OnFailure::Fallback {
+        let res = newton_bisection(&f, HybridOptions{
+            lower: bisection.lower,
+            upper: bisection.upper,
+            patience: opts.patience,
+            tolerance: opts.tolerance,
+            target: opts.target
+        });
+        if let Some(root) = res.root.filter(|root| inside(*root) && domain(*root)) {
+            return Ok((root, res));
+        }
+    }
+    match outside {
+        Some(root) => Err(RootError::OutsideBracket(root)),
+        None => Err(RootError::NotConverged)
+    }
+}
+
+/// The guesses that `root_search` starts from, evenly spaced across the bracket from its lower end
+pub(crate) fn even_guesses<T>(bisection: Interval<T>) -> Vec<T> where T: DualNumFloat {
+    // A bracket only a few floats wide cannot hold a hundred distinct guesses
+    let guesses = effective_resolution(bisection.lower, bisection.upper, 100);
+    let step = (bisection.upper - bisection.lower) / T::from(guesses).unwrap();
+    (0..guesses).map(|i| bisection.lower + (T::from(i).unwrap() * step)).collect()
+}
+
+/// Searches for roots within `radius` of `point`, returning them sorted by their distance from `point`.
+///
+/// The bounds of `opts` are replaced by `[point - radius, point + radius]`.
+pub fn roots_near<F, N, T>(f: F, point: T, radius: T, opts: RootSearchOptions<T>) -> Vec<T>
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    let mut roots = root_search(f, RootSearchOptions{
+        lower: point - radius,
+        upper: point + radius,
+        ..opts
+    }).roots;
+    roots.sort_by(|a, b| (*a - point).abs().partial_cmp(&(*b - point).abs()).unwrap());
+    roots
+}
+
+/// Searches for roots as in `root_search`, collecting them into a map keyed by the root quantised
+/// to the tolerance, i.e. `(root / tolerance).round()`.
+///
+/// Roots sharing a key are treated as duplicates and only the first one found is kept, so the
+/// values of the map are unique and sorted in ascending order. Note that two roots closer together
+/// than the tolerance can still land either side of a rounding boundary and be kept separately.
+///
+/// A root that cannot be keyed, see `RootKey::new`, is left out of the map with a warning.
+pub fn root_search_set<F, N, T>(f: F, opts: RootSearchOptions<T>) -> BTreeMap<i64, T>
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    let tolerance = opts.tolerance;
+    let mut roots = BTreeMap::new();
+    for root in root_search(f, opts).roots {
+        match RootKey::new(root, tolerance) {
+            Some(key) => {
+                roots.entry(key.key).or_insert(root);
+            },
+            None => log::warn!("Root {} cannot be quantised to a tolerance of {}, leaving it out of the set", root, tolerance)
+        }
+    }
+    roots
+}
+
+/// A root quantised to the tolerance, i.e. keyed by `(root / tolerance).round()` as in
+/// `root_search_set`, whose equality and hash only depend on the key, so that roots can be
+/// collected into a `HashSet` with near-duplicates merged.
+///
+/// Roots less than a tolerance apart usually share a key, but two roots either side of a rounding
+/// boundary are kept apart however close they are, while roots almost a tolerance apart can still
+/// be merged. Keys are only comparable between roots quantised to the same tolerance.
+#[derive(Debug, Clone, Copy)]
+pub struct RootKey<T> where T: DualNumFloat {
+    pub root: T,
+    key: i64
+}
+
+impl <T: DualNumFloat> RootKey<T> {
+    /// Keys `root` by its quantised value, or returns `None` where that is not an `i64`, i.e. for
+    /// a root too large for the tolerance, a non-finite root or a tolerance of zero
+    pub fn new(root: T, tolerance: T) -> Option<Self> {
+        (root / tolerance).round().to_i64().map(|key| RootKey{root, key})
+    }
+}
+
+impl <T: DualNumFloat> PartialEq for RootKey<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl <T: DualNumFloat> Eq for RootKey<T> {}
+
+impl <T: DualNumFloat> Hash for RootKey<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key.hash(state);
+    }
+}
+
+/// Searches for roots as in `root_search`, also returning every grid point sampled while bracketing
+/// along with the value of `f` there, in ascending order, for plotting the function and its roots together.
+///
+/// The symmetry hint of `opts` is ignored, so that the samples cover the full bounds.
+pub fn roots_dense<F, N, T>(f: F, opts: RootSearchOptions<T>) -> (RootSearchResult<T>, Vec<(T, T)>)
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    let opts = validated(opts);
+    let scan = find_brackets_in_domain(&f, |_| true, opts.bisection_options());
+    // A flat function has no roots to report, as in `root_search`
+    if scan.range.is_some_and(|(min, max)| max - min <= opts.tolerance) {
+        return (RootSearchResult::empty(), scan.samples);
+    }
+    let mut res = solve_brackets(&f, &|_| true, &even_guesses, scan.brackets, &opts);
+    add_boundary_roots(&f, &|_| true, &mut res, &opts);
+    (res, scan.samples)
+}
+
+/// Whether a root is a simple zero of the function or one of higher multiplicity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Multiplicity {
+    /// The function crosses the target with a non-zero slope, so Newton's method converges quadratically
+    Simple,
+    /// The function is tangent to the target, so Newton's method only converges linearly
+    Multiple
+}
+
+/// The ratio of successive residuals above which Newton's method is taken to converge linearly,
+/// which for a root of multiplicity `m` tends to `((m - 1) / m)^m`, i.e. a quarter for a double root
+const MULTIPLE_ROOT_RATE: f64 = 0.1;
+
+/// Searches for roots as in `root_search`, labelling each of them as a `Simple` or `Multiple` root.
+///
+/// A root is `Multiple` when the grid samples either side of it have the same sign, or when the
+/// residuals of a Newton solve started from the sample below it shrink only linearly. Roots that
+/// touch the target without crossing it, which have no bracket, are found by solving from every
+/// sample that is closer to the target than both of its neighbours on the same side of it.
+pub fn solve_and_classify<F, N, T>(f: F, opts: RootSearchOptions<T>) -> Vec<(T, Multiplicity)>
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    let (res, samples) = roots_dense(&f, opts);
+    let samples: Vec<(T, T)> = samples.into_iter().map(|(x, fx)| (x, fx - opts.target)).collect();
+    let solve = |guess: T| newton_verbose(&f, opts.newton_options(guess));
+    let linear = |residuals: &[T]| {
+        let residuals: Vec<T> = residuals.iter().map(|residual| residual.abs()).filter(|residual| *residual > T::zero()).collect();
+        residuals.len() >= 2 && residuals[residuals.len() - 1] / residuals[residuals.len() - 2] > T::from(MULTIPLE_ROOT_RATE).unwrap()
+    };
+    let mut classified = Vec::new();
+    for root in &res.roots {
+        let below = samples.iter().rev().find(|(x, _)| x < root);
+        let above = samples.iter().find(|(x, _)| x > root);
+        let tangent = matches!((below, above), (Some((_, fa)), Some((_, fb))) if (*fa > T::zero()) == (*fb > T::zero()));
+        let rate = below.or(above).is_some_and(|(guess, _)| linear(&solve(*guess).residuals));
+        classified.push((*root, if tangent || rate { Multiplicity::Multiple } else { Multiplicity::Simple }));
+    }
+    for window in samples.windows(3) {
+        let ((a, fa), (b, fb), (c, fc)) = (window[0], window[1], window[2]);
+        let same_side = (fa > T::zero()) == (fb > T::zero()) && (fb > T::zero()) == (fc > T::zero());
+        if fb == T::zero() || !same_side || fb.abs() >= fa.abs() || fb.abs() > fc.abs() {
+            continue;
+        }
+        let verbose = solve(b);
+        if let Some(root) = verbose.result.root {
+            if a < root && root < c && res.roots.iter().all(|found| (*found - root).abs() > opts.tolerance) {
+                classified.push((root, Multiplicity::Multiple));
+            }
+        }
+    }
+    classified.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    classified
+}
+
+/// Searches for roots as in `root_search`, then refines each of them with Newton's method until
+/// its last step, which estimates its error, is within `digits` significant digits of the root.
+///
+/// This replaces the absolute tolerance of `opts` with the relative tolerance `10^-digits`. A root
+/// that fails to refine, such as one lying exactly at zero, is kept as the search found it.
+pub fn roots_refined_to<F, N, T>(f: F, digits: i32, opts: RootSearchOptions<T>) -> RootSearchResult<T>
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    let relative = T::from(10).unwrap().powi(-digits);
+    // The roots are only rounded once they have been refined
+    let mut res = root_search(&f, RootSearchOptions{round_to_decimals: None, ..opts});
+    for root in res.roots.iter_mut() {
+        let refined = newton(&f, NewtonOptions{tolerance: relative * root.abs(), ..opts.newton_options(*root)});
+        if let Some(refined) = refined.root {
+            *root = refined;
+        }
+    }
+    finish_roots(&mut res, &opts);
+    res
+}
+
+/// Searches for roots as in `root_search`, doubling the resolution of `opts` until two consecutive
+/// resolutions find the same roots to within the tolerance, or until it would exceed `max_resolution`.
+///
+/// Returns the coarser of the two agreeing resolutions along with its result, or the last
+/// resolution tried if the roots never settled. The resolution of `opts` should be coarse, as the
+/// doubling only starts from it.
+pub fn root_search_auto_resolution<F, N, T>(f: F, opts: RootSearchOptions<T>, max_resolution: i32) -> (i32, RootSearchResult<T>)
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    let mut resolution = opts.resolution.max(1);
+    let mut res = root_search(&f, RootSearchOptions{resolution, ..opts});
+    while let Some(finer) = resolution.checked_mul(2).filter(|finer| *finer <= max_resolution) {
+        let next = root_search(&f, RootSearchOptions{resolution: finer, ..opts});
+        let settled = next.roots.len() == res.roots.len()
+            && next.roots.iter().zip(&res.roots).all(|(a, b)| (*a - *b).abs() <= opts.tolerance);
+        if settled {
+            break;
+        }
+        resolution = finer;
+        res = next;
+    }
+    (resolution, res)
+}
+
+/// Searches for the roots of `f(x, parameter)` as in `root_search`, pairing each root with its
+/// `root_sensitivity` to the parameter.
+pub fn root_search_sensitivities<F, N, T>(f: F, parameter: T, opts: RootSearchOptions<T>) -> Result<Vec<(T, T)>, CoerceError>
+where
+    F: Fn(N, N) -> N + Sync + Send,
+    N: Derivable<T> + DualFactory<T> + Display + Clone + Sub + Div + Sync + Send,
+    T: DualNumFloat
+{
+    let fixed = N::seeded(parameter, T::zero())?;
+    let roots = root_search(|x: N| f(x, fixed.clone()), opts).roots;
+    roots.into_iter()
+        .map(|root| Ok((root, root_sensitivity(&f, root, parameter)?)))
+        .collect()
+}
+
+/// The brackets of a search that are still to be solved, from which `resume_search` carries on
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SearchCursor<T> where T: DualNumFloat {
+    /// The unsolved brackets, in ascending order
+    pub brackets: Vec<(Interval<T>, Crossing)>
+}
+
+/// The roots found so far by a resumable search, along with where to resume it
+pub struct PartialSearch<T> where T: DualNumFloat {
+    pub result: RootSearchResult<T>,
+    /// `None` once every bracket has been solved
+    pub cursor: Option<SearchCursor<T>>
+}
+
+/// Searches for roots as in `root_search`, but solves no more than `max_brackets` of the brackets
+/// before returning a cursor, which `resume_search` takes to continue the search later on.
+///
+/// The roots of every partial result, taken in order, are those of the full search. The bounds are
+/// validated as in `root_search`, while the symmetry hint and `max_total_roots` are ignored.
+pub fn root_search_resumable<F, N, T>(f: F, max_brackets: usize, opts: RootSearchOptions<T>) -> PartialSearch<T>
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    let opts = validated(opts);
+    let scan = find_brackets_in_domain(&f, |_| true, opts.bisection_options());
+    // A flat function has no roots to report, as in `root_search`
+    if scan.range.is_some_and(|(min, max)| max - min <= opts.tolerance) {
+        return PartialSearch{
+            result: RootSearchResult::empty(),
+            cursor: None
+        };
+    }
+    let mut partial = resume_search(&f, SearchCursor{brackets: scan.brackets}, max_brackets, opts);
+    // Collapsing the bounds onto the lower one checks only it, as the upper one comes last
+    add_boundary_roots(&f, &|_| true, &mut partial.result, &RootSearchOptions{upper: opts.lower, max_total_roots: None, ..opts});
+    partial
+}
+
+/// Continues a search from the cursor of `root_search_resumable`, solving no more than
+/// `max_brackets` of its brackets. `opts` should be those that the search was started with.
+pub fn resume_search<F, N, T>(f: F, cursor: SearchCursor<T>, max_brackets: usize, opts: RootSearchOptions<T>) -> PartialSearch<T>
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
+    T: DualNumFloat
+{
+    let opts = RootSearchOptions{max_total_roots: None, ..opts};
+    let mut brackets = cursor.brackets;
+    let rest = brackets.split_off(max_brackets.min(brackets.len()));
+    let mut result = solve_brackets(&f, &|_| true, &even_guesses, brackets, &opts);
+    if !rest.is_empty() {
+        finish_roots(&mut result, &opts);
+        return PartialSearch{result, cursor: Some(SearchCursor{brackets: rest})};
+    }
+    add_boundary_roots(&f, &|_| true, &mut result, &RootSearchOptions{lower: opts.upper, ..opts});
+    PartialSearch{result, cursor: None}
+}
+
+/// An iterator over the roots of a function, solving each bracket only once the next root is asked for.
+///
+/// The grid is scanned as soon as the iterator is created, but the Newton solves, which make up most
+/// of the work, are run lazily, so stopping early skips the remaining brackets.
+pub struct RootSearchIter<F, N, T> where T: DualNumFloat {
+    f: F,
+    brackets: IntoIter<(Interval<T>, Crossing)>,
+    opts: RootSearchOptions<T>,
+    /// The number of roots yielded so far, counted against `opts.max_total_roots`
+    yielded: usize,
+    dual: PhantomData<N>
+}
+
+impl <F, N, T> Iterator for RootSearchIter<F, N, T>
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone,
+    T: DualNumFloat
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.opts.max_total_roots.is_some_and(|max| self.yielded >= max) {
+            return None;
+        }
+        for (bisection, crossing) in self.brackets.by_ref() {
+            if crossing != Crossing::Touching && is_pole(&self.f, &bisection, self.opts.target) {
+                continue;
+            }
+            if let Ok(root) = solve_bisection(&self.f, &bisection, &self.opts) {
+                self.yielded += 1;
+                return Some(self.opts.rounded(root));
+            }
+        }
+        None
+    }
+}
+
+/// Searches for roots as in `root_search`, yielding them in ascending order as each one is solved.
+///
+/// The bounds are validated as in `root_search`, while the symmetry hint and roots found outside of
+/// their bracket are ignored.
+pub fn root_search_iter<F, N, T>(f: F, opts: RootSearchOptions<T>) -> RootSearchIter<F, N, T>
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    let opts = validated(opts);
+    let brackets: Vec<(Interval<T>, Crossing)> = find_brackets_in_domain(&f, |_| true, opts.bisection_options()).brackets.into_iter()
+        .filter(|(_, crossing)| opts.direction.is_none_or(|direction| direction == *crossing))
+        .collect();
+    RootSearchIter{f, brackets: brackets.into_iter(), opts, yielded: 0, dual: PhantomData}
+}
+
+/// An iterator adapter that suppresses every root lying within `tolerance` of one already yielded.
+///
+/// The roots yielded so far are kept sorted, so each new root is checked against its neighbours
+/// with a binary search.
+pub struct RootStream<I, T> where T: DualNumFloat {
+    roots: I,
+    tolerance: T,
+    emitted: Vec<T>,
+    /// Stop once this many roots have been yielded
+    limit: Option<usize>
+}
+
+impl <I, T> RootStream<I, T> where I: Iterator<Item = T>, T: DualNumFloat {
+    pub fn new(roots: I, tolerance: T) -> Self {
+        RootStream{roots, tolerance, emitted: Vec::new(), limit: None}
+    }
+}
+
+impl <I, T> Iterator for RootStream<I, T> where I: Iterator<Item = T>, T: DualNumFloat {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.limit.is_some_and(|limit| self.emitted.len() >= limit) {
+            return None;
+        }
+        for root in self.roots.by_ref() {
+            let index = self.emitted.partition_point(|emitted| *emitted < root);
+            let below = index > 0 && root - self.emitted[index - 1] <= self.tolerance;
+            let above = index < self.emitted.len() && self.emitted[index] - root <= self.tolerance;
+            if !below && !above {
+                self.emitted.insert(index, root);
+                return Some(root);
+            }
+        }
+        None
+    }
+}
+
+/// Streams the roots of `root_search_iter`, skipping any within the tolerance of one already yielded.
+///
+/// `max_total_roots` counts the roots that are streamed, so the skipped ones do not use up the limit.
+pub fn roots_stream<F, N, T>(f: F, opts: RootSearchOptions<T>) -> RootStream<RootSearchIter<F, N, T>, T>
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    let stream = RootStream::new(root_search_iter(f, RootSearchOptions{max_total_roots: None, ..opts}), opts.tolerance);
+    RootStream{limit: opts.max_total_roots, ..stream}
+}
+
+/// A root found by `root_search_report` along with how it was found
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RootReport<T> where T: DualNumFloat {
+    pub root: T,
+    /// The value of `f(root) - target`
+    pub residual: T,
+    /// The iterations taken by the Newton solve that converged to the root, or zero for a root on
+    /// one of the bounds
+    pub iterations: i32,
+    pub termination: TerminationReason,
+    pub crossing: Crossing,
+    /// How far the root can be trusted, see `confidence`
+    pub confidence: T,
+    /// The wall-clock time spent solving the bracket of the root
+    pub elapsed: Duration
+}
+
+/// Scores how far a root can be trusted from the residual and the iterations of its solve.
+///
+/// The score is one for a root with no residual found in a single iteration, halves as the
+/// residual grows to the tolerance, and falls to zero once the iterations exceed the patience.
+pub fn confidence<T>(residual: T, iterations: i32, patience: i32, tolerance: T) -> T where T: DualNumFloat {
+    let accuracy = T::one() / (T::one() + residual.abs() / tolerance);
+    let speed = T::one() - T::from(iterations - 1).unwrap() / T::from(patience.max(1)).unwrap();
+    (accuracy * speed).max(T::zero())
+}
+
+/// Every diagnostic gathered over a full root search, as returned by `root_search_report`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SolverReport<T> where T: DualNumFloat {
+    pub roots: Vec<RootReport<T>>,
+    /// The brackets that were solved to a root inside of them, in the same order as `roots`
+    pub resolved: Vec<Interval<T>>,
+    /// The brackets in which no root could be found
+    pub unresolved: Vec<Interval<T>>,
+    /// The brackets over which the sign change is a pole rather than a root, as in `RootSearchResult::poles`
+    pub poles: Vec<Interval<T>>,
+    /// The number of times `f` was evaluated, not counting the evaluation of the residuals
+    pub evaluations: usize,
+    pub elapsed: Duration
+}
+
+impl <T: DualNumFloat> SolverReport<T> {
+    /// Orders the roots, along with the brackets they were resolved from, from most to least confident
+    pub fn sort_by_confidence(&mut self) {
+        let mut pairs: Vec<(RootReport<T>, Interval<T>)> = self.roots.drain(..).zip(self.resolved.drain(..)).collect();
+        pairs.sort_by(|a, b| b.0.confidence.partial_cmp(&a.0.confidence).unwrap_or(std::cmp::Ordering::Equal));
+        (self.roots, self.resolved) = pairs.into_iter().unzip();
+    }
+
+    /// Summarises the iterations taken by the Newton solves of the resolved brackets, or `None`
+    /// if no bracket was resolved
+    pub fn iteration_stats(&self) -> Option<IterationStats> {
+        let mut counts: Vec<i32> = self.roots.iter().map(|root| root.iterations).collect();
+        counts.sort();
+        let (min, max) = (*counts.first()?, *counts.last()?);
+        let mean = counts.iter().map(|count| *count as f64).sum::<f64>() / counts.len() as f64;
+        let mid = counts.len() / 2;
+        let median = if counts.len().is_multiple_of(2) { (counts[mid - 1] + counts[mid]) as f64 / 2.0 } else { counts[mid] as f64 };
+        let mut histogram = BTreeMap::new();
+        for count in counts {
+            *histogram.entry(count).or_insert(0) += 1;
+        }
+        Some(IterationStats{min, max, mean, median, histogram})
+    }
+}
+
+/// The distribution of the iterations taken across the brackets of a search, see `SolverReport::iteration_stats`
+#[derive(Debug, Clone, PartialEq)]
+pub struct IterationStats {
+    pub min: i32,
+    pub max: i32,
+    pub mean: f64,
+    pub median: f64,
+    /// The number of brackets resolved with each count of iterations
+    pub histogram: BTreeMap<i32, usize>
+}
+
+/// Solves the brackets one after another as `InOrder` does, recording the Newton solve of each
+/// bracket and how long it took for `root_search_report`
+struct RecordedSolves<T>(RefCell<Vec<RecordedSolve<T>>>) where T: DualNumFloat;
+
+/// A bracket along with the root and the Newton solve it was solved to, if any, and the time taken
+type RecordedSolve<T> = (Interval<T>, Option<(T, NewtonResult<T>)>, Duration);
+
+impl <T: DualNumFloat> SolveBrackets<T> for RecordedSolves<T> {
+    fn solve_brackets<W>(&self, count: usize, solve: W, enough: &mut dyn FnMut(&Solved<T>) -> bool) -> Vec<Solved<T>>
+    where
+        W: Fn(usize) -> Solved<T> + Sync
+    {
+        solve_in_order(count, |i| {
+            let start = Instant::now();
+            let solved = solve(i);
+            let outcome = solved.outcome.as_ref().ok().map(|(root, res)| (*root, NewtonResult{root: res.root, iterations: res.iterations, termination: res.termination}));
+            self.0.borrow_mut().push((solved.bracket, outcome, start.elapsed()));
+            solved
+        }, enough)
+    }
+}
+
+/// Searches for roots as in `root_search`, returning a `SolverReport` of everything that happened.
+///
+/// The bounds are validated as in `root_search`, while the symmetry hint, roots found outside of
+/// their bracket and `exhaust_brackets` are ignored.
+pub fn root_search_report<F, N, T>(f: F, opts: RootSearchOptions<T>) -> SolverReport<T>
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    let start = Instant::now();
+    // The roots found besides the first of a bracket have no Newton solve of their own to report
+    let opts = RootSearchOptions{exhaust_brackets: false, ..validated(opts)};
+    let evaluations = AtomicUsize::new(0);
+    let counted = |x: N| {
+        evaluations.fetch_add(1, Ordering::Relaxed);
+        f(x)
+    };
+    let solves = RecordedSolves(RefCell::new(Vec::new()));
+    // A flat function has no roots to report, as in `root_search`
+    let mut result = search_brackets(counted, &|_| true, &even_guesses, opts, &solves).unwrap_or_else(|_| RootSearchResult::empty());
+    add_boundary_roots(&counted, &|_| true, &mut result, &opts);
+    let residual = |root: T| match from_scalar::<N, T>(root) {
+        Ok(x) => f(x).zeroth_derivative() - opts.target,
+        Err(_) => T::nan()
+    };
+    let (solved, failed): (Vec<_>, Vec<_>) = solves.0.into_inner().into_iter().partition(|(_, outcome, _)| outcome.is_some());
+    let mut solved = solved.into_iter().peekable();
+    let mut roots = Vec::new();
+    let mut resolved = Vec::new();
+    for (root, crossing) in result.roots.iter().zip(&result.crossings) {
+        // The residual is taken at the root as solved, before it was rounded
+        let (bracket, unrounded, res, elapsed) = match solved.next_if(|(_, outcome, _)| outcome.as_ref().is_some_and(|(unrounded, _)| opts.rounded(*unrounded) == *root)) {
+            Some((bracket, Some((unrounded, res)), elapsed)) => (bracket, unrounded, res, elapsed),
+            // Any other root is one of the bounds, which takes no Newton solve
+            _ => {
+                let bound = if opts.rounded(opts.lower) == *root { opts.lower } else { opts.upper };
+                (Interval::new(bound, bound), bound, NewtonResult{root: Some(bound), iterations: 0, termination: TerminationReason::ToleranceMet}, Duration::ZERO)
+            }
+        };
+        let residual = residual(unrounded);
+        roots.push(RootReport{
+            root: *root,
+            residual,
+            iterations: res.iterations,
+            termination: res.termination,
+            crossing: *crossing,
+            confidence: confidence(residual, res.iterations.max(1), opts.patience, opts.tolerance),
+            elapsed
+        });
+        resolved.push(bracket);
+    }
+    SolverReport{
+        roots,
+        resolved,
+        unresolved: failed.into_iter().map(|(bracket, _, _)| bracket).collect(),
+        poles: result.poles,
+        evaluations: evaluations.load(Ordering::Relaxed),
+        elapsed: start.elapsed()
+    }
+}
+
+/// The tightest tolerance the single precision pass of `root_search_refined` is run with, as a
+/// finer one cannot be met at single precision and is left to the double precision polish
+const COARSE_TOLERANCE: f32 = 1e-4;
+
+/// Searches for roots in single precision and then polishes each of them in double precision.
+///
+/// The bracketing scan and the bulk of the Newton iterations are run on the cheaper `coarse`
+/// function, after which each root is used as the initial guess of at most `polish_steps` Newton
+/// iterations on `fine`, which should be the same function evaluated in `f64`. Roots that fail to
+/// polish are kept at single precision.
+pub fn root_search_refined<F, G>(coarse: F, fine: G, opts: RootSearchOptions<f64>, polish_steps: i32) -> RootSearchResult<f64>
+where
+    F: Fn(Dual32) -> Dual32 + Sync + Send,
+    G: Fn(Dual64) -> Dual64 + Sync + Send
+{
+    refine_coarse_roots(coarse, &opts, |roots| roots.into_iter().map(|root| polish_root(&fine, root, &opts, polish_steps)).collect())
+}
+
+/// Runs the single precision pass of `root_search_refined`, then converts its result to double
+/// precision with the roots replaced by `polish` of them
+pub(crate) fn refine_coarse_roots<F, P>(coarse: F, opts: &RootSearchOptions<f64>, polish: P) -> RootSearchResult<f64>
+where
+    F: Fn(Dual32) -> Dual32 + Sync + Send,
+    P: Fn(Vec<f32>) -> Vec<f64>
+{
+    let res = root_search::<_, Dual32, f32>(coarse, RootSearchOptions{
+        patience: opts.patience,
+        tolerance: (opts.tolerance as f32).max(COARSE_TOLERANCE),
+        lower: opts.lower as f32,
+        upper: opts.upper as f32,
+        resolution: opts.resolution,
+        target: opts.target as f32,
+        auto_order_bounds: opts.auto_order_bounds,
+        keep_outside_bracket: opts.keep_outside_bracket,
+        direction: opts.direction,
+        symmetry: opts.symmetry,
+        subdivisions: opts.subdivisions,
+        sign_epsilon: opts.sign_epsilon as f32,
+        boundary_roots: opts.boundary_roots,
+        max_total_roots: opts.max_total_roots,
+        on_failure: opts.on_failure,
+        exhaust_brackets: opts.exhaust_brackets,
+        inclusive_bounds: opts.inclusive_bounds,
+        plateau_tolerance: opts.plateau_tolerance.map(|tolerance| tolerance as f32),
+        // The roots are only rounded once they have been polished
+        round_to_decimals: None
+    });
+    let roots = polish(res.roots).into_iter().map(|root| opts.rounded(root)).collect();
+    RootSearchResult{
+        roots,
+        bisections: res.bisections.into_iter().map(|b| Interval::new(b.lower as f64, b.upper as f64)).collect(),
+        crossings: res.crossings,
+        outside_bracket_roots: polish(res.outside_bracket_roots),
+        poles: res.poles.into_iter().map(|p| Interval::new(p.lower as f64, p.upper as f64)).collect(),
+        plateaus: res.plateaus.into_iter().map(|p| p as f64).collect()
+    }
+}
+
+/// Polishes a single precision root in double precision as `root_search_refined` does, keeping it
+/// at single precision if it fails to polish
+pub(crate) fn polish_root<G>(fine: &G, root: f32, opts: &RootSearchOptions<f64>, polish_steps: i32) -> f64
+where
+    G: Fn(Dual64) -> Dual64 + Sync + Send
+{
+    newton::<_, Dual64, f64>(fine, NewtonOptions{patience: polish_steps, ..opts.newton_options(root as f64)}).root.unwrap_or(root as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_dual::{Dual32, Dual64, DualNum};
+
+    /// Checks the roots of `sin` over `[-10, 10]` against properties computed in `T` itself, rather
+    /// than against constants of a particular float type
+    fn check_sine_roots<N, T>()
+    where
+        N: DualNum<T> + Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+        T: DualNumFloat
+    {
+        let opts = RootSearchOptions{
+            lower: T::from(-10.0).unwrap(),
+            upper: T::from(10.0).unwrap(),
+            ..Default::default()
+        };
+        let res = root_search::<_,N,T>(|x: N| x.sin(), opts);
+        let half = (opts.upper / T::PI()).floor().to_usize().unwrap();
+        assert_eq!(res.roots.len(), 2 * half + 1);
+        for root in &res.roots {
+            assert!(root.sin().abs() < opts.tolerance);
+            let multiple = (*root / T::PI()).round();
+            assert!((*root - multiple * T::PI()).abs() < opts.tolerance);
+        }
+        assert!(res.roots.windows(2).all(|pair| ((pair[1] - pair[0]) - T::PI()).abs() < opts.tolerance));
+    }
+
+    /// Checks that Newton's method finds `sqrt(2)` as the positive root of `x^2 - 2` in any float type
+    fn check_square_root<N, T>()
+    where
+        N: DualNum<T> + Derivable<T> + Coerceable<T> + Display + Clone,
+        T: DualNumFloat
+    {
+        let two = T::from(2.0).unwrap();
+        let res = newton::<_,N,T>(|x: N| x.powi(2) - two, NewtonOptions{
+            guess: T::one(),
+            ..Default::default()
+        });
+        let root = res.root.unwrap();
+        assert!((root * root - two).abs() < T::from(0.0001).unwrap());
+        assert!((root - T::SQRT_2()).abs() < T::from(0.0001).unwrap());
+    }
+
+    #[test]
+    fn find_roots_in_any_float_type() {
+        check_sine_roots::<Dual32,f32>();
+        check_sine_roots::<Dual64,f64>();
+        check_square_root::<Dual32,f32>();
+        check_square_root::<Dual64,f64>();
+    }
+
+    #[test]
+    fn convert_between_scalar_and_dual() {
+        let x: Dual32 = from_scalar(1.5_f64).unwrap();
+        assert_eq!(x.re, 1.5);
+        assert_eq!(x.eps, 0.0);
+        assert_eq!(to_scalar::<_, f64>(&x), Ok(1.5));
+        let y = Dualize::<f64, Dual32>::to_dual(2.5).unwrap();
+        assert_eq!(y, Dual32::from_re(2.5));
+        assert_eq!(Dualize::<f64, Dual32>::from_dual(&y), Ok(2.5));
+        assert_eq!(Dualize::<f64, Dual32>::to_dual(f64::NAN), Err(CoerceError::NotRepresentable));
+    }
+
+    #[test]
+    fn seed_dual_derivatives() {
+        let x: Dual64 = Dual64::seeded(2.0_f64, 3.0).unwrap();
+        assert_eq!(x.powi(2).first_derivative(), 12.0);
+        let x: Dual32 = Dual32::seeded(2.0_f64, 0.5).unwrap();
+        assert_eq!(x.powi(2).first_derivative(), 2.0);
+        assert_eq!(Dual32::seeded(1.0_f64, f64::NAN).err(), Some(CoerceError::NotRepresentable));
+    }
+
+    #[test]
+    fn find_roots_of_composed_functions() {
+        use std::f32::consts::FRAC_PI_2;
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        fn cosine<D: DualNum<f32>>(x: D) -> D {
+            x.cos()
+        }
+        let composed = compose(sine::<Dual32>, cosine::<Dual32>);
+        let slope = composed(Dual32::from_re(1.0).derivative()).eps;
+        assert!((slope + 1.0_f32.cos().cos() * 1.0_f32.sin()).abs() < 0.0001);
+        let res = root_search::<_,Dual32,f32>(composed, RootSearchOptions{
+            lower: -5.0,
+            upper: 5.0,
+            ..Default::default()
+        });
+        assert_eq!(res.roots.len(), 4);
+        for (root, k) in res.roots.iter().zip([-3.0, -1.0, 1.0, 3.0]) {
+            assert!((root - k * FRAC_PI_2).abs() < 0.0001);
+        }
+    }
+
+    #[test]
+    fn reject_unrepresentable_scalars() {
+        assert_eq!(from_scalar::<Dual32, f64>(f64::NAN).err(), Some(CoerceError::NotRepresentable));
+        assert_eq!(from_scalar::<Dual32, f64>(1e300).err(), Some(CoerceError::NotRepresentable));
+        let res = newton::<_,Dual32,f32>(|x: Dual32| x - 1.0, NewtonOptions{
+            guess: f32::NAN,
+            ..Default::default()
+        });
+        assert_eq!(res.termination, TerminationReason::NonFinite);
+    }
+
+    #[test]
+    fn reject_non_finite_inputs() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let nan_bounds = RootSearchOptions{
+            lower: f32::NAN,
+            upper: 5.0,
+            ..Default::default()
+        };
+        assert_eq!(try_root_search::<_,Dual32,f32>(sine, nan_bounds).err(), Some(RootSearchError::NonFinite));
+        let infinite = RootSearchOptions{upper: f32::INFINITY, ..Default::default()};
+        assert_eq!(try_root_search::<_,Dual32,f32>(sine, infinite).err(), Some(RootSearchError::NonFinite));
+        let res = newton::<_,Dual32,f32>(sine, NewtonOptions{
+            guess: 3.0,
+            tolerance: f32::NAN,
+            ..Default::default()
+        });
+        assert_eq!(res.termination, TerminationReason::NonFinite);
+        assert_eq!(res.iterations, 0);
+    }
+
+    #[test]
+    fn check_reported_derivatives() {
+        #[derive(Clone)]
+        struct DoubledSlope(Dual32);
+        impl Derivable<f32> for DoubledSlope {
+            fn execute_derivative(&self) -> Self {
+                DoubledSlope(self.0.derivative())
+            }
+            fn zeroth_derivative(&self) -> f32 {
+                self.0.re
+            }
+            fn first_derivative(&self) -> f32 {
+                2.0 * self.0.eps
+            }
+        }
+        impl Coerceable<f32> for DoubledSlope {
+            fn coerce_to(&self) -> Result<f32, CoerceError> {
+                Ok(self.0.re)
+            }
+            fn coerce_from(value: f32) -> Result<Self, CoerceError> {
+                Ok(DoubledSlope(Dual32::from_re(value)))
+            }
+        }
+        let check = check_derivative::<_,Dual32,f32>(|x: Dual32| x.sin(), 1.0, 0.001).unwrap();
+        assert_eq!(check.reported, 1.0_f32.cos());
+        assert!(check.discrepancy < 0.001);
+        let check = check_derivative::<_,DoubledSlope,f32>(|x: DoubledSlope| DoubledSlope(x.0.sin()), 1.0, 0.001).unwrap();
+        assert!((check.discrepancy - 1.0_f32.cos()).abs() < 0.001);
+    }
+
+    #[test]
+    fn find_sine_roots_with_non_copy_dual() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let res = newton::<_,DualDVec32,f32>(&sine, NewtonOptions{
+            guess: 2.0,
+            ..Default::default()
+        });
+        assert_eq!(res.root, Some(std::f32::consts::PI));
+        let res = root_search::<_,DualDVec32,f32>(&sine, RootSearchOptions{
+            lower: -5.0,
+            upper: 5.0,
+            ..Default::default()
+        });
+        assert_eq!(res.roots.len(), 3);
+        assert!(res.roots.contains(&std::f32::consts::PI));
+    }
+
+    #[test]
+    fn find_sine_root_newton() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let res = newton::<_,Dual32,f32>(&sine, NewtonOptions{
+            guess: 2.0,
+            patience: 1000,
+            tolerance: 0.0001,
+            ..Default::default()
+        });
+        assert_eq!(std::f32::consts::PI, res.root.unwrap());
+        assert_eq!(res.termination, TerminationReason::ToleranceMet)
+    }
+
+    #[test]
+    fn refine_rough_sine_roots() {
+        use std::f32::consts::PI;
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let refined = refine_candidates::<_,Dual32,f32>(sine, &[-3.0, 0.1, 3.2], NewtonOptions{
+            tolerance: 0.000001,
+            ..Default::default()
+        });
+        assert_eq!(refined, vec![Some(-PI), Some(0.0), Some(PI)]);
+        let failed = refine_candidates::<_,Dual32,f32>(sine, &[3.1, f32::NAN], Default::default());
+        assert_eq!(failed, vec![Some(PI), None]);
+    }
+
+    #[test]
+    fn solve_known_sine_bracket() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let opts = RootSearchOptions::default();
+        let res = solve_with_initial_bracket::<_,Dual32,f32>(sine, &Interval::new(2.5, 4.0), &opts);
+        assert_eq!(res.termination, TerminationReason::ToleranceMet);
+        assert!((res.root.unwrap() - std::f32::consts::PI).abs() < 0.0001);
+        let res = solve_with_initial_bracket::<_,Dual32,f32>(sine, &Interval::new(0.5, 2.5), &opts);
+        assert_eq!(res.termination, TerminationReason::NotBracketed);
+    }
+
+    #[test]
+    fn read_slope_at_sine_root() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let (res, dual) = newton_dual::<_,Dual32,f32>(&sine, NewtonOptions{
+            guess: 2.0,
+            ..Default::default()
+        });
+        assert_eq!(res.root, Some(std::f32::consts::PI));
+        let dual = dual.unwrap();
+        assert!(dual.zeroth_derivative().abs() < 0.0001);
+        assert!((dual.first_derivative() + 1.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn record_sine_residuals() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let verbose = newton_verbose::<_,Dual32,f32>(sine, NewtonOptions{
+            guess: 2.0,
+            ..Default::default()
+        });
+        assert_eq!(verbose.result.root, Some(std::f32::consts::PI));
+        assert_eq!(verbose.residuals.len(), verbose.result.iterations as usize);
+        assert_eq!(verbose.residuals[0], 2.0_f32.sin());
+        assert!(verbose.residuals.last().unwrap().abs() < 0.0001);
+        assert_eq!(verbose.iterates.len(), verbose.residuals.len());
+        assert_eq!(verbose.iterates[0], 2.0);
+    }
+
+    #[test]
+    fn record_sine_iterates() {
+        use std::f32::consts::PI;
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let path = newton_path::<_,Dual32,f32>(sine, NewtonOptions{
+            guess: 2.0,
+            ..Default::default()
+        });
+        assert_eq!(path.iterates[0], 2.0);
+        assert_eq!(path.iterates.len(), path.result.iterations as usize + 1);
+        assert!((path.iterates.last().unwrap() - PI).abs() < 0.0001);
+        assert!(path.iterates.windows(2).all(|pair| (pair[1] - PI).abs() <= (pair[0] - PI).abs()));
+    }
+
+    #[test]
+    fn spend_newton_evaluation_budget() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+        let evaluations = AtomicU32::new(0);
+        let sine = |x: Dual32| {
+            evaluations.fetch_add(1, Ordering::Relaxed);
+            x.sin()
+        };
+        let mut budget = 100;
+        let res = newton_budgeted::<_,Dual32,f32>(&sine, 2.0, &mut budget, 0.0001);
+        assert_eq!(res.root, Some(std::f32::consts::PI));
+        assert_eq!(budget, 100 - evaluations.load(Ordering::Relaxed));
+        assert_eq!(budget, 100 - res.iterations as u32);
+        let mut budget = 2;
+        let res = newton_budgeted::<_,Dual32,f32>(&sine, 2.0, &mut budget, 0.0001);
+        assert_eq!(res.root, None);
+        assert_eq!(res.termination, TerminationReason::BudgetExhausted);
+        assert_eq!(budget, 0);
+    }
+
+    #[test]
+    fn stop_when_residual_stagnates() {
+        // Rounding in the expanded cube leaves the residual near the triple root far noisier than the target
+        fn triple_root<D: DualNum<f32>>(x: D) -> D {
+            x.powi(3) - x.powi(2) * 3.0 + x * 3.0 - 1.0
+        }
+        let res = newton::<_,Dual32,f32>(triple_root, NewtonOptions{
+            guess: 2.0,
+            patience: 1000,
+            tolerance: 0.000000001,
+            target: 0.000000001,
+            ..Default::default()
+        });
+        assert_eq!(res.termination, TerminationReason::Stagnated);
+        assert!(res.iterations < 1000);
+    }
+
+    #[test]
+    fn cap_newton_steps_across_flat_region() {
+        fn arctan<D: DualNum<f64>>(x: D) -> D {
+            x.atan()
+        }
+        let plain = newton::<_,Dual64,f64>(arctan, NewtonOptions{
+            guess: 2.0,
+            ..Default::default()
+        });
+        assert_eq!(plain.root, None);
+        let capped = newton::<_,Dual64,f64>(arctan, NewtonOptions{
+            guess: 2.0,
+            max_step: Some(0.5),
+            ..Default::default()
+        });
+        assert_eq!(capped.termination, TerminationReason::ToleranceMet);
+        assert!(capped.root.unwrap().abs() < 0.0001);
+    }
+
+    #[test]
+    fn take_minimum_iterations_in_flat_region() {
+        // The quintic is so flat around its root that the steps fall below a loose tolerance far from it
+        fn quintic<D: DualNum<f64>>(x: D) -> D {
+            (x - 1.0).powi(5)
+        }
+        let early = newton::<_,Dual64,f64>(quintic, NewtonOptions{
+            guess: 2.0,
+            tolerance: 0.1,
+            ..Default::default()
+        });
+        assert_eq!(early.iterations, 5);
+        assert!((early.root.unwrap() - 1.0).abs() > 0.3);
+        let res = newton::<_,Dual64,f64>(quintic, NewtonOptions{
+            guess: 2.0,
+            tolerance: 0.1,
+            min_iterations: 30,
+            ..Default::default()
+        });
+        assert_eq!(res.termination, TerminationReason::ToleranceMet);
+        assert_eq!(res.iterations, 30);
+        assert!((res.root.unwrap() - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn converge_at_any_magnitude_with_relative_tolerance() {
+        // The roots of `x^2 - c` are 1e-6 and 1e6, twelve orders of magnitude apart
+        let square = |c: f64| move |x: Dual64| x * x - c;
+        let opts = || NewtonOptions{
+            guess: 1.0,
+            tolerance: 1e-15,
+            relative_tolerance: 1e-9,
+            ..Default::default()
+        };
+        for root in [1e-6, 1e6] {
+            let res = newton(square(root * root), opts());
+            assert_eq!(res.termination, TerminationReason::ToleranceMet);
+            assert!((res.root.unwrap() - root).abs() < 1e-8 * root);
+        }
+        // An absolute tolerance coarse enough for the large root stops far short of the small one
+        let absolute = newton(square(1e-12), NewtonOptions{tolerance: 1e-3, relative_tolerance: 0.0, ..opts()});
+        assert!((absolute.root.unwrap() - 1e-6).abs() > 1e-6);
+    }
+
+    #[test]
+    fn require_small_residual_to_converge() {
+        // The steps shrink with the distance to the root, so they are small long before the value is
+        fn cubic<D: DualNum<f64>>(x: D) -> D {
+            (x - 1.0).powi(3)
+        }
+        let opts = || NewtonOptions{
+            guess: 3.0,
+            tolerance: 0.1,
+            ..Default::default()
+        };
+        let step_only = newton::<_,Dual64,f64>(cubic, opts());
+        let root = step_only.root.unwrap();
+        assert!((root - 1.0).powi(3) > 0.001);
+        let tolerance = Tolerance{step: 0.1, residual: 1e-6};
+        let res = newton::<_,Dual64,f64>(cubic, opts().with_tolerance(tolerance));
+        assert_eq!(res.termination, TerminationReason::ToleranceMet);
+        assert!((res.root.unwrap() - 1.0).powi(3) < 1e-6);
+        assert!(res.iterations > step_only.iterations);
+        let residual_only = newton::<_,Dual64,f64>(cubic, opts().with_tolerance(Tolerance{step: f64::INFINITY, ..tolerance}));
+        assert_eq!(residual_only.termination, TerminationReason::ToleranceMet);
+        assert!(residual_only.iterations <= res.iterations);
+        assert_eq!(residual_only.root, res.root);
+    }
+
+    #[test]
+    fn converge_where_plain_newton_diverges() {
+        fn arctan<D: DualNum<f64>>(x: D) -> D {
+            x.atan()
+        }
+        let opts = || NewtonOptions{
+            guess: 2.0,
+            tolerance: 1e-10,
+            ..Default::default()
+        };
+        assert!(newton::<_,Dual64,f64>(arctan, opts()).root.is_none());
+        let res = newton_trust_region::<_,Dual64,f64>(arctan, 1.0, opts());
+        assert_eq!(res.termination, TerminationReason::ToleranceMet);
+        assert!(res.root.unwrap().abs() < 1e-10);
+        // Without a root to find, the iterates settle at the minimum of the residual instead of running away
+        fn shifted_square<D: DualNum<f64>>(x: D) -> D {
+            x.powi(2) + 1.0
+        }
+        let res = newton_trust_region::<_,Dual64,f64>(shifted_square, 1.0, opts());
+        assert!(res.root.is_none());
+        assert!(matches!(res.termination, TerminationReason::ZeroDerivative | TerminationReason::Stagnated));
+    }
+
+    #[test]
+    fn solve_sine_to_residual() {
+        fn sine<D: DualNum<f64>>(x: D) -> D {
+            x.sin()
+        }
+        let res = newton_to_residual::<_,Dual64,f64>(sine, 1e-8, NewtonOptions{
+            guess: 3.0,
+            ..Default::default()
+        });
+        assert_eq!(res.termination, TerminationReason::ToleranceMet);
+        assert!(res.root.unwrap().sin().abs() < 1e-8);
+        // A loose residual is met long before a tight step would be
+        let loose = newton_to_residual::<_,Dual64,f64>(sine, 0.1, NewtonOptions{
+            guess: 3.1,
+            ..Default::default()
+        });
+        assert_eq!(loose.iterations, 1);
+    }
+
+    #[test]
+    fn reuse_dual_buffer_across_newton_iterations() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+        /// A dual number whose derivative lives on the heap, counting every box it allocates
+        #[derive(Clone)]
+        struct Boxed {
+            re: f64,
+            eps: Box<f64>
+        }
+        impl Boxed {
+            fn new(re: f64, eps: f64) -> Self {
+                ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+                Boxed{re, eps: Box::new(eps)}
+            }
+        }
+        impl Display for Boxed {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "{} + {}ε", self.re, self.eps)
+            }
+        }
+        impl Derivable<f64> for Boxed {
+            fn execute_derivative(&self) -> Self {
+                Boxed::new(self.re, 1.0)
+            }
+            fn zeroth_derivative(&self) -> f64 {
+                self.re
+            }
+            fn first_derivative(&self) -> f64 {
+                *self.eps
+            }
+        }
+        impl Coerceable<f64> for Boxed {
+            fn coerce_to(&self) -> Result<f64, CoerceError> {
+                Ok(self.re)
+            }
+            fn coerce_from(value: f64) -> Result<Self, CoerceError> {
+                Ok(Boxed::new(value, 0.0))
+            }
+            fn reseed_in_place(&mut self, value: f64) -> Result<(), CoerceError> {
+                self.re = value;
+                *self.eps = 1.0;
+                Ok(())
+            }
+        }
+        // x^2 - 2, written to update its argument rather than allocate a result
+        let square_minus_two = |mut x: Boxed| {
+            *x.eps *= 2.0 * x.re;
+            x.re = x.re * x.re - 2.0;
+            x
+        };
+        let opts = || NewtonOptions{
+            guess: 3.0,
+            tolerance: 1e-12,
+            ..Default::default()
+        };
+        let res = newton::<_,Boxed,f64>(square_minus_two, opts());
+        assert!(res.iterations > 2);
+        assert_eq!(ALLOCATIONS.load(Ordering::SeqCst), 2);
+        let dual = newton::<_,Dual64,f64>(|x: Dual64| x * x - 2.0, opts());
+        assert_eq!(res.root, dual.root);
+        assert_eq!(res.iterations, dual.iterations);
+    }
+
+    #[test]
+    fn stop_at_unachievable_tolerance() {
+        use std::f32::consts::FRAC_PI_2;
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            (x * 10.0).sin()
+        }
+        // The iterates end up alternating between two neighbouring floats around the root
+        let res = newton::<_,Dual32,f32>(sine, NewtonOptions{
+            guess: 1.5,
+            tolerance: 1e-30,
+            ..Default::default()
+        });
+        assert_eq!(res.termination, TerminationReason::ToleranceUnachievable);
+        assert!((res.root.unwrap() - FRAC_PI_2).abs() < 1e-6);
+        assert!(res.iterations < 10);
+    }
+
+    #[test]
+    fn detect_newton_two_cycle() {
+        // Newton's method maps 0 to 1 and 1 back to 0 on this cubic
+        fn cubic<D: DualNum<f32>>(x: D) -> D {
+            x.powi(3) - x * 2.0 + 2.0
+        }
+        let res = newton::<_,Dual32,f32>(cubic, NewtonOptions{
+            guess: 0.0,
+            patience: 1000,
+            ..Default::default()
+        });
+        assert_eq!(res.termination, TerminationReason::Cycling);
+        assert_eq!(res.iterations, 2);
+        let res = newton::<_,Dual32,f32>(cubic, NewtonOptions{
+            guess: -2.0,
+            ..Default::default()
+        });
+        assert_eq!(res.termination, TerminationReason::ToleranceMet);
+    }
+
+    #[test]
+    fn estimate_sine_root_basins() {
+        use std::f32::consts::{FRAC_PI_2, PI};
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let solver = Newton::new();
+        for root in [-PI, 0.0, PI] {
+            let basin = solver.basin::<_,Dual32>(&sine, root, 0.01, 5.0);
+            assert!(basin.contains(root));
+            assert!(basin.width() > 1.0);
+            assert!(root - FRAC_PI_2 < basin.lower && basin.upper < root + FRAC_PI_2);
+        }
+    }
+
+    #[test]
+    fn find_cosine_root_newton() {
+        fn cosine<D: DualNum<f32>>(x: D) -> D {
+            x.cos()
+        }
+        let res = newton::<_,Dual32,f32>(&cosine, NewtonOptions{
+            guess: 2.0,
+            patience: 1000,
+            tolerance: 0.0001,
+            ..Default::default()
+        });
+        assert_eq!(std::f32::consts::PI / 2.0, res.root.unwrap())
+    }
+
+    #[test]
+    fn reuse_configured_newton_solver() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        fn cosine<D: DualNum<f32>>(x: D) -> D {
+            x.cos()
+        }
+        let solver = Newton::new().with_patience(1000).with_tolerance(0.0001);
+        assert_eq!(solver.solve::<_,Dual32>(&sine, 2.0).root, Some(std::f32::consts::PI));
+        assert_eq!(solver.solve::<_,Dual32>(&cosine, 2.0).root, Some(std::f32::consts::FRAC_PI_2));
+        assert_eq!(solver.solve::<_,Dual32>(&sine, 0.5).root, Some(0.0));
+    }
+
+    #[test]
+    fn newton_termination_reasons() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        fn shifted_square<D: DualNum<f32>>(x: D) -> D {
+            x.powi(2) + 1.0
+        }
+        fn cube_root<D: DualNum<f32>>(x: D) -> D {
+            x.cbrt()
+        }
+        let exhausted = newton::<_,Dual32,f32>(&sine, NewtonOptions{
+            guess: 2.0,
+            patience: 1,
+            tolerance: 0.0001,
+            ..Default::default()
+        });
+        assert_eq!(exhausted.root, None);
+        assert_eq!(exhausted.termination, TerminationReason::PatienceExhausted);
+        let flat = newton::<_,Dual32,f32>(&shifted_square, NewtonOptions{
+            guess: 0.0,
+            patience: 1000,
+            tolerance: 0.0001,
+            ..Default::default()
+        });
+        assert_eq!(flat.termination, TerminationReason::ZeroDerivative);
+        let diverged = newton::<_,Dual32,f32>(&cube_root, NewtonOptions{
+            guess: 1.0,
+            patience: 1000,
+            tolerance: 0.0001,
+            ..Default::default()
+        });
+        assert_eq!(diverged.termination, TerminationReason::Diverged);
+    }
+
+    #[test]
+    fn bound_sine_roots_by_final_bracket() {
+        use std::f64::consts::PI;
+        fn sine<D: DualNum<f64>>(x: D) -> D {
+            x.sin()
+        }
+        let opts = RootSearchOptions{
+            lower: -10.0,
+            upper: 10.0,
+            tolerance: 0.000001,
+            ..Default::default()
+        };
+        let roots = roots_with_bracket_width::<_,Dual64,f64>(sine, opts);
+        assert_eq!(roots.len(), 7);
+        for (root, width) in roots {
+            assert!(width <= opts.tolerance);
+            assert!((root - (root / PI).round() * PI).abs() <= width);
+        }
+    }
+
+    #[test]
+    fn find_arctangent_root_newton_bisection() {
+        fn arctangent<D: DualNum<f32>>(x: D) -> D {
+            x.atan()
+        }
+        let plain = newton::<_,Dual32,f32>(&arctangent, NewtonOptions{
+            guess: 2.0,
+            ..Default::default()
+        });
+        assert!(plain.root.is_none_or(|root| !(-1.0 < root && root < 5.0)));
+        let res = newton_bisection::<_,Dual32,f32>(&arctangent, HybridOptions{
+            lower: -1.0,
+            upper: 5.0,
+            ..Default::default()
+        });
+        assert_eq!(res.termination, TerminationReason::ToleranceMet);
+        assert!(res.root.unwrap().abs() < 0.0001);
+    }
+
+    #[test]
+    fn interval_helpers() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let interval = Interval::new(3.0_f32, 4.0);
+        assert_eq!(interval.midpoint(), 3.5);
+        assert_eq!(interval.width(), 1.0);
+        assert!(interval.contains(std::f32::consts::PI));
+        assert!(!interval.contains(4.5));
+        assert!(interval.has_sign_change::<_,Dual32>(&sine));
+        assert_eq!(interval.crossing::<_,Dual32>(&sine), Some(Crossing::Falling));
+        assert!(!Interval::new(1.0_f32, 2.0).has_sign_change::<_,Dual32>(&sine));
+    }
+
+    #[test]
+    fn find_sine_bisections() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let bisections = find_bisections::<_,Dual32,f32>(&sine, BisectionOptions{
+            lower: -5.0, 
+            upper: 5.0, 
+            resolution: 1000,
+            ..Default::default()
+        });
+        for bisection in &bisections {
+            println!("bisection: ({},{})", bisection.lower, bisection.upper)
+        }
+        assert_eq!(bisections.len(), 3)
+    }
+
+    #[test]
     fn find_cosine_bisections() {
         fn cosine<D: DualNum<f32>>(x: D) -> D {
             x.cos()
         }
-        let bisections = find_bisections::<_,Dual32,f32>(&cosine, BisectionOptions{
-            lower: -5.0, 
-            upper: 5.0, 
-            resolution: 1000
+        let bisections = find_bisections::<_,Dual32,f32>(&cosine, BisectionOptions{
+            lower: -5.0, 
+            upper: 5.0, 
+            resolution: 1000,
+            ..Default::default()
+        });
+        for bisection in &bisections {
+            println!("bisection: ({},{})", bisection.lower, bisection.upper)
+        }
+        assert_eq!(bisections.len(), 4)
+    }
+
+    #[test]
+    fn bracket_root_on_grid_point() {
+        let (lower, upper, resolution) = (-1.0_f32, 1.0_f32, 10);
+        // The fifth point of the grid, computed as `find_brackets_in_domain` computes it
+        let grid_root = lower + (upper - lower) * 4.0 / resolution as f32;
+        let shifted = move |x: Dual32| x - grid_root;
+        let grid = || BisectionOptions{
+            lower,
+            upper,
+            resolution,
+            ..Default::default()
+        };
+        let samples = find_brackets_in_domain::<_,_,Dual32,f32>(&shifted, |_| true, grid()).samples;
+        assert!(samples.iter().any(|(x, _)| *x == grid_root));
+        let bisections = find_bisections::<_,Dual32,f32>(&shifted, grid());
+        assert_eq!(bisections.len(), 1);
+        assert!(bisections[0].lower < grid_root && grid_root < bisections[0].upper);
+        let res = root_search::<_,Dual32,f32>(&shifted, RootSearchOptions{
+            lower,
+            upper,
+            patience: 1000,
+            tolerance: 0.0001,
+            resolution,
+            ..Default::default()
+        });
+        assert_eq!(res.roots, vec![grid_root]);
+    }
+
+    #[test]
+    fn label_tangent_root_on_grid_point() {
+        let square = |x: Dual32| x * x;
+        let opts = RootSearchOptions{
+            lower: -1.0,
+            upper: 1.0,
+            resolution: 10,
+            ..Default::default()
+        };
+        let res = root_search::<_,Dual32,f32>(square, opts);
+        assert_eq!(res.roots.len(), 1);
+        assert!(res.roots[0].abs() < 0.01);
+        assert_eq!(res.crossings, vec![Crossing::Touching]);
+        for direction in [Crossing::Rising, Crossing::Falling] {
+            assert!(root_search::<_,Dual32,f32>(square, RootSearchOptions{direction: Some(direction), ..opts}).roots.is_empty());
+        }
+        // A root on a grid point that the function crosses keeps its direction
+        let res = root_search::<_,Dual32,f32>(|x: Dual32| -x, opts);
+        assert_eq!(res.crossings, vec![Crossing::Falling]);
+    }
+
+    #[test]
+    fn cap_unrepresentable_resolution() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        let evaluations = AtomicUsize::new(0);
+        let shifted = |x: Dual32| {
+            evaluations.fetch_add(1, Ordering::Relaxed);
+            x - 1.00005
+        };
+        let bisections = find_bisections::<_,Dual32,f32>(&shifted, BisectionOptions{
+            lower: 1.0,
+            upper: 1.0001,
+            resolution: 1_000_000,
+            ..Default::default()
+        });
+        assert!(evaluations.load(Ordering::Relaxed) < 2_000);
+        assert_eq!(bisections.len(), 1);
+        assert!(bisections[0].lower < 1.00005 && 1.00005 < bisections[0].upper);
+    }
+
+    #[test]
+    fn sample_grid_exactly_within_bounds() {
+        fn shifted<D: DualNum<f32>>(x: D) -> D {
+            x - 0.7
+        }
+        let scan = find_brackets_in_domain::<_,_,Dual32,f32>(shifted, |_| true, BisectionOptions{
+            lower: 0.1,
+            upper: 0.7,
+            resolution: 3,
+            ..Default::default()
+        });
+        assert!(scan.samples.iter().all(|(x, _)| (0.1..=0.7).contains(x)));
+        let points: Vec<f32> = scan.samples.iter().map(|(x, _)| *x).collect();
+        let expected: Vec<f32> = (0..=3).map(|i| if i == 3 { 0.7 } else { 0.1 + (0.7_f32 - 0.1) * i as f32 / 3.0 }).collect();
+        assert_eq!(points, expected);
+        let res = root_search::<_,Dual32,f32>(shifted, RootSearchOptions{
+            lower: 0.1,
+            upper: 0.7,
+            resolution: 3,
+            boundary_roots: true,
+            ..Default::default()
+        });
+        assert_eq!(res.roots, vec![0.7]);
+    }
+
+    #[test]
+    fn search_narrow_range_at_high_resolution() {
+        fn shifted<D: DualNum<f32>>(x: D) -> D {
+            x - 1.000001
+        }
+        // The grid step is no wider than the spacing of the floats, so only a few points are distinct
+        let bisections = find_bisections::<_,Dual32,f32>(shifted, BisectionOptions{
+            lower: 1.0,
+            upper: 1.000001,
+            resolution: 1_000_000,
+            ..Default::default()
+        });
+        assert_eq!(bisections.len(), 1);
+        assert!(bisections[0].lower < bisections[0].upper);
+        let res = root_search::<_,Dual32,f32>(shifted, RootSearchOptions{
+            lower: 0.999999,
+            upper: 1.000002,
+            resolution: 1_000_000,
+            tolerance: 0.0000001,
+            ..Default::default()
+        });
+        assert_eq!(res.roots.len(), 1);
+        assert!((res.roots[0] - 1.000001).abs() < 0.0000002);
+    }
+
+    #[test]
+    fn evaluate_grid_points_once() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        let evaluations = AtomicUsize::new(0);
+        let shifted = |x: Dual32| {
+            evaluations.fetch_add(1, Ordering::Relaxed);
+            x - 0.5
+        };
+        let bisections = find_bisections::<_,Dual32,f32>(&shifted, BisectionOptions{
+            lower: 0.0,
+            upper: 1.0,
+            resolution: 100,
+            ..Default::default()
+        });
+        assert_eq!(evaluations.load(Ordering::Relaxed), 101);
+        assert_eq!(bisections.len(), 1);
+    }
+
+    #[test]
+    fn report_flat_function() {
+        fn constant<D: DualNum<f32>>(x: D) -> D {
+            x * 0.0 + 2.0
+        }
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let opts = RootSearchOptions{
+            lower: -5.0,
+            upper: 5.0,
+            ..Default::default()
+        };
+        assert_eq!(try_root_search::<_,Dual32,f32>(&constant, opts).err(), Some(RootSearchError::FlatFunction));
+        assert!(root_search::<_,Dual32,f32>(&constant, opts).roots.is_empty());
+        assert_eq!(try_root_search::<_,Dual32,f32>(&sine, opts).unwrap().roots.len(), 3);
+        let reversed = RootSearchOptions{lower: 5.0, upper: -5.0, ..opts};
+        assert_eq!(try_root_search::<_,Dual32,f32>(&sine, reversed).err(), Some(RootSearchError::BoundsReversed));
+    }
+
+    #[test]
+    fn find_sine_roots_from_midpoint_guesses() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let res = root_search_with_guesses::<_,_,Dual32,f32>(sine, |bracket: Interval<f32>| vec![bracket.midpoint()], RootSearchOptions{
+            lower: -5.0,
+            upper: 5.0,
+            ..Default::default()
+        });
+        assert_eq!(res.roots.len(), 3);
+        for (root, k) in res.roots.iter().zip([-1.0, 0.0, 1.0]) {
+            assert!((root - k * std::f32::consts::PI).abs() < 0.0001);
+        }
+    }
+
+    #[test]
+    fn fold_periodic_sine_search() {
+        use std::f32::consts::PI;
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let periodic = periodic_root_search::<_,Dual32,f32>(sine, 2.0 * PI, RootSearchOptions::default());
+        assert_eq!(periodic.roots.len(), 2);
+        assert!(periodic.roots[0].abs() < 0.0001);
+        assert!((periodic.roots[1] - PI).abs() < 0.0001);
+        assert_eq!(periodic.crossings, vec![Crossing::Rising, Crossing::Falling]);
+        let roots = periodic.within(-10.0, 20.0).unwrap();
+        assert_eq!(roots.len(), 10);
+        for (root, k) in roots.iter().zip(-3..=6) {
+            assert!((root - k as f32 * PI).abs() < 0.0001);
+        }
+        assert_eq!(periodic.within(f32::MIN, f32::MAX), Err(RootSearchError::TooManyPeriods));
+        assert_eq!(periodic.within(0.0, 1e9), Err(RootSearchError::TooManyPeriods));
+        assert_eq!(periodic.within(f32::NEG_INFINITY, 0.0), Err(RootSearchError::NonFinite));
+        assert_eq!(periodic.within(1.0, 0.0), Err(RootSearchError::BoundsReversed));
+    }
+
+    #[test]
+    fn bracket_root_on_noisy_grid_sample() {
+        use std::f32::consts::FRAC_PI_2;
+        fn cosine<D: DualNum<f32>>(x: D) -> D {
+            x.cos()
+        }
+        // The middle sample lands on `FRAC_PI_2`, where the cosine rounds to a tiny negative value
+        // rather than zero, but the root rounds to the sample itself, so it is bracketed as a zero
+        let upper = 2.0 * FRAC_PI_2 - 1.0;
+        let opts = RootSearchOptions{
+            lower: 1.0,
+            upper,
+            resolution: 2,
+            ..Default::default()
+        };
+        assert!(cosine(FRAC_PI_2) < 0.0);
+        let res = root_search::<_,Dual32,f32>(cosine, opts);
+        assert_eq!(res.roots, vec![FRAC_PI_2]);
+        assert_eq!(res.bisections, vec![Interval::new(1.0, upper)]);
+        let res = root_search::<_,Dual32,f32>(cosine, RootSearchOptions{sign_epsilon: 0.000001, ..opts});
+        assert_eq!(res.roots, vec![FRAC_PI_2]);
+    }
+
+    #[test]
+    fn classify_simple_and_double_roots() {
+        fn cubic<D: DualNum<f64>>(x: D) -> D {
+            (x.clone() - 1.0) * (x - 3.0).powi(2)
+        }
+        let roots = solve_and_classify::<_,Dual64,f64>(cubic, RootSearchOptions{
+            lower: 0.0,
+            upper: 4.1,
+            ..Default::default()
+        });
+        assert_eq!(roots.len(), 2);
+        assert!((roots[0].0 - 1.0).abs() < 0.0001);
+        assert!((roots[1].0 - 3.0).abs() < 0.001);
+        assert_eq!(roots.iter().map(|(_, multiplicity)| *multiplicity).collect::<Vec<_>>(), vec![Multiplicity::Simple, Multiplicity::Multiple]);
+        // A triple root changes sign like a simple one, so only its slow convergence sets it apart
+        fn triple<D: DualNum<f64>>(x: D) -> D {
+            (x - 2.0).powi(3)
+        }
+        let roots = solve_and_classify::<_,Dual64,f64>(triple, RootSearchOptions{
+            lower: 0.0,
+            upper: 4.1,
+            ..Default::default()
+        });
+        assert_eq!(roots.len(), 1);
+        assert_eq!(roots[0].1, Multiplicity::Multiple);
+    }
+
+    #[test]
+    fn bracket_roots_on_irregular_grid() {
+        fn shifted_square<D: DualNum<f32>>(x: D) -> D {
+            x.powi(2) - 2.0
+        }
+        let points = [-3.0, -2.9, -0.5, 0.1, 1.0, 1.3, 1.5, 4.0];
+        let brackets = find_bisections_on::<_,Dual32,f32>(shifted_square, &points);
+        assert_eq!(brackets, vec![Interval::new(-2.9, -0.5), Interval::new(1.3, 1.5)]);
+        // A root on the first point is bracketed as it is by `find_bisections`
+        let on_first = find_bisections_on::<_,Dual32,f32>(|x| x - 1.0, &[1.0, 1.5, 2.0]);
+        assert_eq!(on_first, vec![Interval::new(1.0, 1.5)]);
+        let root = solve_bisection::<_,Dual32,f32>(shifted_square, &brackets[1], &RootSearchOptions::default()).unwrap();
+        assert!((root - 2.0f32.sqrt()).abs() < 0.0001);
+    }
+
+    #[test]
+    fn keep_lowest_roots_up_to_cap() {
+        use std::f32::consts::PI;
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let opts = RootSearchOptions{
+            lower: 0.0,
+            upper: 20.0,
+            boundary_roots: true,
+            max_total_roots: Some(2),
+            ..Default::default()
+        };
+        let res = root_search::<_,Dual32,f32>(sine, opts);
+        assert_eq!(res.roots.len(), 2);
+        assert_eq!(res.roots[0], 0.0);
+        assert!((res.roots[1] - PI).abs() < 0.0001);
+        let mirrored = root_search::<_,Dual32,f32>(sine, RootSearchOptions{
+            lower: -20.0,
+            symmetry: Symmetry::Odd,
+            ..opts
+        });
+        assert_eq!(mirrored.roots.len(), 2);
+        assert!((mirrored.roots[0] + 6.0 * PI).abs() < 0.0001);
+        assert!((mirrored.roots[1] + 5.0 * PI).abs() < 0.0001);
+    }
+
+    #[test]
+    fn resume_sine_search_from_checkpoints() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let opts = RootSearchOptions{
+            lower: 0.0,
+            upper: 10.0 * std::f32::consts::PI,
+            ..Default::default()
+        };
+        let mut partial = root_search_resumable::<_,Dual32,f32>(sine, 3, opts);
+        let mut roots = partial.result.roots.clone();
+        let mut checkpoints = 0;
+        while let Some(cursor) = partial.cursor {
+            checkpoints += 1;
+            partial = resume_search::<_,Dual32,f32>(sine, cursor, 3, opts);
+            roots.extend(&partial.result.roots);
+        }
+        assert_eq!(checkpoints, 3);
+        assert_eq!(roots, root_search::<_,Dual32,f32>(sine, opts).roots);
+    }
+
+    #[test]
+    fn verify_candidate_sine_roots() {
+        use std::f32::consts::PI;
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        assert!(is_root::<_,Dual32,f32>(sine, PI, 0.0001));
+        assert!(is_root::<_,Dual32,f32>(sine, PI + 0.00005, 0.0001));
+        assert!(!is_root::<_,Dual32,f32>(sine, 1.0, 0.0001));
+        fn square<D: DualNum<f32>>(x: D) -> D {
+            x.powi(2)
+        }
+        assert!(is_root::<_,Dual32,f32>(square, 0.001, 0.0001));
+        assert!(!is_root::<_,Dual32,f32>(square, 0.1, 0.0001));
+    }
+
+    #[test]
+    fn keep_root_on_bracket_endpoint_when_inclusive() {
+        fn identity<D: DualNum<f32>>(x: D) -> D {
+            x
+        }
+        let bracket = Interval::new(0.0, 1.0);
+        let strict = solve_bisection::<_,Dual32,f32>(identity, &bracket, &RootSearchOptions::default());
+        assert!(matches!(strict, Err(RootError::OutsideBracket(root)) if root == 0.0));
+        let inclusive = solve_bisection::<_,Dual32,f32>(identity, &bracket, &RootSearchOptions{
+            inclusive_bounds: true,
+            ..Default::default()
+        });
+        assert!(matches!(inclusive, Ok(root) if root == 0.0));
+    }
+
+    #[test]
+    fn span_sine_roots_with_hull() {
+        use std::f32::consts::PI;
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let res = root_search::<_,Dual32,f32>(sine, RootSearchOptions{
+            lower: -5.0,
+            upper: 5.0,
+            ..Default::default()
+        });
+        let hull = roots_hull(&res.roots).unwrap();
+        assert!((hull.lower + PI).abs() < 0.0001);
+        assert!((hull.upper - PI).abs() < 0.0001);
+        assert_eq!(roots_hull(&[2.0, -1.0, 0.5]), Some(Interval::new(-1.0, 2.0)));
+        assert_eq!(roots_hull::<f32>(&[]), None);
+    }
+
+    #[test]
+    fn round_roots_to_four_decimals() {
+        // The roots are the cube roots of 0.5 and 2, i.e. 0.793700.. and 1.259921..
+        fn cubic<D: DualNum<f32>>(x: D) -> D {
+            (x.powi(3) - 0.5) * (x.powi(3) - 2.0)
+        }
+        let opts = RootSearchOptions{
+            lower: 0.0,
+            upper: 2.0,
+            ..Default::default()
+        };
+        let rounded = RootSearchOptions{round_to_decimals: Some(4), ..opts};
+        let res = root_search::<_,Dual32,f32>(cubic, rounded);
+        assert_eq!(res.roots, vec![0.7937, 1.2599]);
+        assert_ne!(root_search::<_,Dual32,f32>(cubic, opts).roots, res.roots);
+        let mut partial = root_search_resumable::<_,Dual32,f32>(cubic, 1, rounded);
+        let mut resumed = partial.result.roots.clone();
+        while let Some(cursor) = partial.cursor {
+            partial = resume_search::<_,Dual32,f32>(cubic, cursor, 1, rounded);
+            resumed.extend(partial.result.roots.iter());
+        }
+        assert_eq!(resumed, res.roots);
+        assert_eq!(roots_stream::<_,Dual32,f32>(cubic, rounded).collect::<Vec<f32>>(), res.roots);
+        let report = root_search_report::<_,Dual32,f32>(cubic, rounded);
+        assert_eq!(report.roots.iter().map(|root| root.root).collect::<Vec<f32>>(), res.roots);
+        let widths = roots_with_bracket_width::<_,Dual32,f32>(cubic, rounded);
+        assert_eq!(widths.iter().map(|(root, _)| *root).collect::<Vec<f32>>(), res.roots);
+        assert_eq!(round_to_decimals(1.23456_f64, 2), 1.23);
+        assert_eq!(round_to_decimals(f64::MAX, 4), f64::MAX);
+    }
+
+    #[test]
+    fn map_uniform_density_of_sine_roots() {
+        use std::f32::consts::PI;
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let density = root_density::<_,Dual32,f32>(sine, 20, BisectionOptions{
+            lower: -100.0,
+            upper: 100.0,
+            resolution: 10000,
+            ..Default::default()
+        });
+        assert_eq!(density.len(), 20);
+        assert_eq!(density[0].0, Interval::new(-100.0, -90.0));
+        assert_eq!(density[19].0.upper, 100.0);
+        // The roots are `PI` apart, so each region of width 10 holds three or four of them
+        assert!(density.iter().all(|(_, count)| (3..=4).contains(count)));
+        assert_eq!(density.iter().map(|(_, count)| count).sum::<usize>(), 2 * (100.0 / PI) as usize + 1);
+    }
+
+    #[test]
+    fn hint_tangent_root_from_grid_scan() {
+        fn one_minus_cosine<D: DualNum<f64>>(x: D) -> D {
+            -x.cos() + 1.0
+        }
+        // Off-centre bounds keep any sample from landing exactly on the root
+        let opts = || BisectionOptions{
+            lower: -4.0,
+            upper: 5.5,
+            ..Default::default()
+        };
+        assert!(find_bisections::<_,Dual64,f64>(one_minus_cosine, opts()).is_empty());
+        let (x, fx) = grid_scan::<_,Dual64,f64>(one_minus_cosine, opts()).unwrap();
+        assert!(x.abs() < 0.01);
+        assert!(fx < 0.0001);
+        let res = newton::<_,Dual64,f64>(one_minus_cosine, NewtonOptions{
+            guess: x,
+            ..Default::default()
+        });
+        assert!(res.root.unwrap().abs() < 0.001);
+    }
+
+    #[test]
+    fn report_plateau_grazing_zero() {
+        fn grazing<D: DualNum<f64>>(x: D) -> D {
+            (x - 1.0).powi(4) + 1e-7
+        }
+        let opts = RootSearchOptions{
+            lower: 0.0,
+            upper: 2.0,
+            ..Default::default()
+        };
+        let res = root_search::<_,Dual64,f64>(grazing, opts);
+        assert!(res.roots.is_empty());
+        assert!(res.plateaus.is_empty());
+        let res = root_search::<_,Dual64,f64>(grazing, RootSearchOptions{plateau_tolerance: Some(1e-5), ..opts});
+        assert!(res.roots.is_empty());
+        assert_eq!(res.plateaus.len(), 1);
+        assert!((res.plateaus[0] - 1.0).abs() < 0.01);
+        // A crossing is a root rather than a plateau, however flat the function is around it
+        fn sine<D: DualNum<f64>>(x: D) -> D {
+            x.sin()
+        }
+        let res = root_search::<_,Dual64,f64>(sine, RootSearchOptions{lower: -5.0, upper: 5.0, plateau_tolerance: Some(0.1), ..opts});
+        assert_eq!(res.roots.len(), 3);
+        assert!(res.plateaus.is_empty());
+    }
+
+    #[test]
+    fn find_roots_of_lookup_table() {
+        struct Table {
+            xs: Vec<f32>,
+            ys: Vec<f32>
+        }
+        impl Evaluate<f32> for Table {
+            fn eval<N: DualNum<f32>>(&self, x: N) -> N {
+                // Interpolates linearly within the segment holding `x`, extending the end segments
+                let i = self.xs.windows(2).position(|pair| x.re() < pair[1]).unwrap_or(self.xs.len() - 2);
+                let slope = (self.ys[i + 1] - self.ys[i]) / (self.xs[i + 1] - self.xs[i]);
+                (x - self.xs[i]) * slope + self.ys[i]
+            }
+        }
+        let table = Table{xs: vec![0.0, 1.0, 2.0, 3.0], ys: vec![-1.0, 1.0, -2.0, 0.5]};
+        let res = root_search_evaluate::<_,Dual32,f32>(&table, RootSearchOptions{
+            lower: 0.0,
+            upper: 3.0,
+            ..Default::default()
+        });
+        assert_eq!(res.roots.len(), 3);
+        for (root, expected) in res.roots.iter().zip([0.5, 4.0 / 3.0, 2.8]) {
+            assert!((root - expected).abs() < 0.0001);
+        }
+    }
+
+    #[test]
+    fn exhaust_bracket_holding_three_roots() {
+        fn cubic<D: DualNum<f32>>(x: D) -> D {
+            (x.clone() - 1.0) * (x.clone() - 1.1) * (x - 1.2)
+        }
+        let opts = RootSearchOptions{
+            lower: 0.5,
+            upper: 1.5,
+            resolution: 1,
+            ..Default::default()
+        };
+        let res = root_search::<_,Dual32,f32>(cubic, opts);
+        assert_eq!(res.bisections.len(), 1);
+        assert_eq!(res.roots.len(), 1);
+        let res = root_search::<_,Dual32,f32>(cubic, RootSearchOptions{exhaust_brackets: true, ..opts});
+        assert_eq!(res.roots.len(), 3);
+        for (root, expected) in res.roots.iter().zip([1.0, 1.1, 1.2]) {
+            assert!((root - expected).abs() < 0.0001);
+        }
+        assert_eq!(res.crossings, vec![Crossing::Rising, Crossing::Falling, Crossing::Rising]);
+        let rising = root_search::<_,Dual32,f32>(cubic, RootSearchOptions{exhaust_brackets: true, direction: Some(Crossing::Rising), ..opts});
+        assert_eq!(rising.roots.len(), 2);
+    }
+
+    #[test]
+    fn limit_roots_in_requested_direction() {
+        // The first bracket rises across the roots at 1, 1.1 and 1.2, the second falls across 3
+        // and the third rises across 4
+        fn quintic<D: DualNum<f32>>(x: D) -> D {
+            (x.clone() - 1.0) * (x.clone() - 1.1) * (x.clone() - 1.2) * (x.clone() - 3.0) * (x - 4.0)
+        }
+        let res = root_search::<_,Dual32,f32>(quintic, RootSearchOptions{
+            lower: 0.5,
+            upper: 4.5,
+            resolution: 4,
+            exhaust_brackets: true,
+            direction: Some(Crossing::Rising),
+            max_total_roots: Some(3),
+            ..Default::default()
+        });
+        assert_eq!(res.roots.len(), 3);
+        for (root, expected) in res.roots.iter().zip([1.0, 1.2, 4.0]) {
+            assert!((root - expected).abs() < 0.0001);
+        }
+        assert_eq!(res.crossings, vec![Crossing::Rising; 3]);
+    }
+
+    #[test]
+    fn shrink_sine_bracket_before_newton() {
+        use std::f32::consts::PI;
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let wide = Interval::new(2.0, 5.0);
+        let tight = shrink_bracket::<_,Dual32,f32>(sine, &wide, 10);
+        assert!(tight.width() < wide.width() / 1000.0);
+        assert!(tight.contains(PI));
+        assert!(tight.has_sign_change(sine::<Dual32>));
+        let flat = Interval::new(0.5, 2.5);
+        assert_eq!(shrink_bracket::<_,Dual32,f32>(sine, &flat, 10), flat);
+        let centred = Interval::new(-1.0, 1.0);
+        assert_eq!(shrink_bracket::<_,Dual32,f32>(sine, &centred, 10), Interval::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn handle_failed_brackets_by_policy() {
+        use std::f32::consts::FRAC_PI_2;
+        fn cosine<D: DualNum<f32>>(x: D) -> D {
+            x.cos()
+        }
+        // The slope vanishes at the first guess of the bracket `[0, 3]`, which abandons it
+        let opts = RootSearchOptions{
+            lower: 0.0,
+            upper: 6.0,
+            resolution: 2,
+            ..Default::default()
+        };
+        let skip = root_search::<_,Dual32,f32>(cosine, opts);
+        assert_eq!(skip.roots.len(), 1);
+        assert!((skip.roots[0] - 3.0 * FRAC_PI_2).abs() < 0.0001);
+        let fallback = root_search::<_,Dual32,f32>(cosine, RootSearchOptions{on_failure: OnFailure::Fallback, ..opts});
+        assert_eq!(fallback.roots.len(), 2);
+        assert!((fallback.roots[0] - FRAC_PI_2).abs() < 0.0001);
+        assert_eq!(fallback.roots[1], skip.roots[0]);
+        let abort = root_search::<_,Dual32,f32>(cosine, RootSearchOptions{on_failure: OnFailure::Abort, ..opts});
+        assert!(abort.roots.is_empty());
+        assert_eq!(abort.bisections, skip.bisections);
+    }
+
+    #[test]
+    fn find_sine_roots_on_bounds() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let opts = RootSearchOptions{
+            lower: 0.0,
+            upper: 5.0,
+            boundary_roots: true,
+            ..Default::default()
+        };
+        let res = root_search::<_,Dual32,f32>(sine, opts);
+        assert_eq!(res.roots.len(), 2);
+        assert_eq!(res.roots[0], 0.0);
+        assert!((res.roots[1] - std::f32::consts::PI).abs() < 0.0001);
+        assert_eq!(res.crossings, vec![Crossing::Rising, Crossing::Falling]);
+        let res = root_search::<_,Dual32,f32>(sine, RootSearchOptions{boundary_roots: false, ..opts});
+        assert_eq!(res.roots.len(), 1);
+        assert_eq!(root_search::<_,Dual32,f32>(sine, RootSearchOptions{lower: 0.0, upper: 5.0, ..Default::default()}).roots, res.roots);
+    }
+
+    #[test]
+    fn separate_tangent_poles_from_roots() {
+        use std::f32::consts::{FRAC_PI_2, PI};
+        use nalgebra::ComplexField;
+        let tangent = |x: Dual32| x.tan();
+        let poles = scan_for_poles::<_,Dual32,f32>(tangent, BisectionOptions{
+            lower: -5.0,
+            upper: 5.0,
+            ..Default::default()
         });
-        for bisection in &bisections {
-            println!("bisection: ({},{})", bisection.lower, bisection.upper)
+        assert_eq!(poles.len(), 4);
+        for (pole, k) in poles.iter().zip([-3.0, -1.0, 1.0, 3.0]) {
+            assert!(pole.contains(k * FRAC_PI_2));
         }
-        assert_eq!(bisections.len(), 4)
+        let res = root_search::<_,Dual32,f32>(tangent, RootSearchOptions{
+            lower: -5.0,
+            upper: 5.0,
+            ..Default::default()
+        });
+        assert_eq!(res.roots, vec![-PI, 0.0, PI]);
+        assert_eq!(res.poles, poles);
+    }
+
+    #[test]
+    fn find_close_root_pair_on_coarse_grid() {
+        fn close_pair<D: DualNum<f32>>(x: D) -> D {
+            (x.clone() - 0.521) * (x - 0.531)
+        }
+        let opts = RootSearchOptions{
+            lower: 0.0,
+            upper: 1.0,
+            resolution: 10,
+            tolerance: 0.00001,
+            ..Default::default()
+        };
+        assert!(root_search::<_,Dual32,f32>(&close_pair, opts).roots.is_empty());
+        let res = root_search::<_,Dual32,f32>(&close_pair, RootSearchOptions{subdivisions: 20, ..opts});
+        assert_eq!(res.roots.len(), 2);
+        assert!((res.roots[0] - 0.521).abs() < 0.0001);
+        assert!((res.roots[1] - 0.531).abs() < 0.0001);
+        assert_eq!(res.crossings, vec![Crossing::Falling, Crossing::Rising]);
     }
 
     #[test]
@@ -252,7 +4468,8 @@ mod tests {
             upper: 5.0,
             patience: 2000,
             tolerance: 0.0001,
-            resolution: 1000
+            resolution: 1000,
+            ..Default::default()
         });
         for root in &res.roots {
             println!("root: {}", root);
@@ -273,7 +4490,8 @@ mod tests {
             upper: 5.0,
             patience: 2000,
             tolerance: 0.0001,
-            resolution: 1000
+            resolution: 1000,
+            ..Default::default()
         });
         for root in &res.roots {
             println!("root: {}", root);
@@ -285,5 +4503,661 @@ mod tests {
         assert!(res.roots.contains(&(-std::f32::consts::FRAC_PI_2 * 3.0)));
     }
 
+    #[test]
+    fn find_sine_root_crossings() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let res = root_search::<_,Dual32,f32>(&sine, RootSearchOptions{
+            lower: -5.0,
+            upper: 5.0,
+            ..Default::default()
+        });
+        assert_eq!(res.crossings, vec![Crossing::Falling, Crossing::Rising, Crossing::Falling]);
+    }
+
+    #[test]
+    fn refine_single_precision_roots() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        let refined_evaluations = AtomicUsize::new(0);
+        let direct_evaluations = AtomicUsize::new(0);
+        let coarse = |x: Dual32| x.sin();
+        let refined = |x: Dual64| {
+            refined_evaluations.fetch_add(1, Ordering::Relaxed);
+            x.sin()
+        };
+        let direct = |x: Dual64| {
+            direct_evaluations.fetch_add(1, Ordering::Relaxed);
+            x.sin()
+        };
+        let opts = RootSearchOptions{
+            lower: -5.0,
+            upper: 5.0,
+            tolerance: 1e-12,
+            ..Default::default()
+        };
+        let res = root_search_refined(coarse, refined, opts, 10);
+        let expected = root_search::<_,Dual64,f64>(direct, opts).roots;
+        assert_eq!(res.roots.len(), expected.len());
+        for (root, expected) in res.roots.iter().zip(&expected) {
+            assert!((root - expected).abs() < 1e-12);
+        }
+        assert!(refined_evaluations.load(Ordering::Relaxed) < direct_evaluations.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn find_sine_roots_with_odd_symmetry() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        let symmetric_evaluations = AtomicUsize::new(0);
+        let full_evaluations = AtomicUsize::new(0);
+        let symmetric_sine = |x: Dual32| {
+            symmetric_evaluations.fetch_add(1, Ordering::Relaxed);
+            x.sin()
+        };
+        let full_sine = |x: Dual32| {
+            full_evaluations.fetch_add(1, Ordering::Relaxed);
+            x.sin()
+        };
+        let opts = RootSearchOptions{
+            lower: -5.0,
+            upper: 5.0,
+            ..Default::default()
+        };
+        let symmetric = root_search::<_,Dual32,f32>(symmetric_sine, RootSearchOptions{symmetry: Symmetry::Odd, ..opts});
+        let full = root_search::<_,Dual32,f32>(full_sine, opts);
+        assert_eq!(symmetric.roots, vec![-std::f32::consts::PI, 0.0, std::f32::consts::PI]);
+        assert_eq!(symmetric.crossings, full.crossings);
+        assert!(symmetric_evaluations.load(Ordering::Relaxed) < full_evaluations.load(Ordering::Relaxed) * 2 / 3);
+    }
+
+    #[test]
+    fn stream_unique_sine_roots() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let opts = RootSearchOptions{
+            lower: -10.0,
+            upper: 10.0,
+            resolution: 10000,
+            ..Default::default()
+        };
+        let streamed: Vec<f32> = roots_stream::<_,Dual32,f32>(&sine, opts).collect();
+        assert_eq!(streamed, root_search::<_,Dual32,f32>(&sine, opts).roots);
+        assert_eq!(streamed.len(), 7);
+        assert_eq!(roots_stream::<_,Dual32,f32>(&sine, opts).take(2).count(), 2);
+        let repeated = RootStream::new(vec![1.0, 0.0, 1.00001, -1.0, 0.00002].into_iter(), 0.0001_f32);
+        assert_eq!(repeated.collect::<Vec<f32>>(), vec![1.0, 0.0, -1.0]);
+    }
+
+    #[test]
+    fn limit_roots_of_lazy_and_reported_searches() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let opts = RootSearchOptions{
+            lower: -10.0,
+            upper: 10.0,
+            max_total_roots: Some(3),
+            ..Default::default()
+        };
+        let lowest = root_search::<_,Dual32,f32>(sine, opts).roots;
+        assert_eq!(lowest.len(), 3);
+        assert_eq!(root_search_iter::<_,Dual32,f32>(sine, opts).collect::<Vec<f32>>(), lowest);
+        assert_eq!(roots_stream::<_,Dual32,f32>(sine, opts).collect::<Vec<f32>>(), lowest);
+        let report = root_search_report::<_,Dual32,f32>(sine, opts);
+        assert_eq!(report.roots.iter().map(|root| root.root).collect::<Vec<f32>>(), lowest);
+        assert_eq!(report.resolved.len(), 3);
+        let limited = RootStream{limit: Some(2), ..RootStream::new(vec![1.0, 1.00001, 2.0, 3.0].into_iter(), 0.0001_f32)};
+        assert_eq!(limited.collect::<Vec<f32>>(), vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn report_sine_root_search() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        let evaluations = AtomicUsize::new(0);
+        let sine = |x: Dual32| {
+            evaluations.fetch_add(1, Ordering::Relaxed);
+            x.sin()
+        };
+        let report = root_search_report::<_,Dual32,f32>(sine, RootSearchOptions{
+            lower: -5.0,
+            upper: 5.0,
+            ..Default::default()
+        });
+        let roots: Vec<f32> = report.roots.iter().map(|root| root.root).collect();
+        assert_eq!(roots, vec![-std::f32::consts::PI, 0.0, std::f32::consts::PI]);
+        assert!(report.roots.iter().all(|root| root.residual.abs() < 0.0001 && root.iterations > 0));
+        assert!(report.roots.iter().all(|root| root.termination == TerminationReason::ToleranceMet));
+        assert_eq!(report.resolved.len(), 3);
+        assert!(report.unresolved.is_empty());
+        assert_eq!(report.evaluations + 3, evaluations.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn report_poles_apart_from_roots() {
+        fn tangent<D: DualNum<f32>>(x: D) -> D {
+            x.tan()
+        }
+        let opts = RootSearchOptions{
+            lower: 1.0,
+            upper: 5.0,
+            ..Default::default()
+        };
+        let res = root_search::<_,Dual32,f32>(tangent, opts);
+        assert_eq!(res.roots.len(), 1);
+        assert_eq!(res.poles.len(), 2);
+        let report = root_search_report::<_,Dual32,f32>(tangent, opts);
+        assert_eq!(report.roots.iter().map(|root| root.root).collect::<Vec<f32>>(), res.roots);
+        assert_eq!(report.poles, res.poles);
+        assert!(report.unresolved.is_empty());
+        assert!(report.resolved[0].contains(std::f32::consts::PI));
+    }
+
+    #[test]
+    fn rank_roots_by_confidence() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let converged = newton_dual::<_,Dual32,f32>(sine, NewtonOptions{
+            guess: 3.0,
+            ..Default::default()
+        });
+        let exhausted = newton_dual::<_,Dual32,f32>(sine, NewtonOptions{
+            guess: 2.0,
+            patience: 1,
+            ..Default::default()
+        });
+        assert_eq!(exhausted.0.termination, TerminationReason::PatienceExhausted);
+        let score = |(res, dual): (NewtonResult<f32>, Option<Dual32>), patience| {
+            confidence(dual.unwrap().zeroth_derivative(), res.iterations, patience, 0.0001)
+        };
+        assert!(score(converged, 1000) > score(exhausted, 1));
+        let mut report = root_search_report::<_,Dual32,f32>(sine, RootSearchOptions{
+            lower: -5.0,
+            upper: 7.0,
+            ..Default::default()
+        });
+        report.sort_by_confidence();
+        assert!(report.roots.windows(2).all(|pair| pair[0].confidence >= pair[1].confidence));
+        assert!(report.roots.iter().zip(&report.resolved).all(|(root, bracket)| bracket.contains(root.root)));
+    }
+
+    #[test]
+    fn summarise_iterations_across_brackets() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let report = root_search_report::<_,Dual32,f32>(sine, RootSearchOptions{
+            lower: -10.0,
+            upper: 10.0,
+            ..Default::default()
+        });
+        let stats = report.iteration_stats().unwrap();
+        assert_eq!(stats.histogram.values().sum::<usize>(), report.resolved.len());
+        assert!(stats.min as f64 <= stats.median && stats.median <= stats.max as f64);
+        assert!(stats.min as f64 <= stats.mean && stats.mean <= stats.max as f64);
+        let flat = root_search_report::<_,Dual32,f32>(|x| x * 0.0 + 1.0, RootSearchOptions::default());
+        assert_eq!(flat.iteration_stats(), None);
+    }
+
+    #[test]
+    fn time_each_bracket_solve() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let report = root_search_report::<_,Dual32,f32>(sine, RootSearchOptions{
+            lower: -10.0,
+            upper: 10.0,
+            ..Default::default()
+        });
+        assert_eq!(report.roots.len(), 7);
+        let solving: Duration = report.roots.iter().map(|root| root.elapsed).sum();
+        assert!(solving <= report.elapsed);
+    }
+
+    #[test]
+    fn sample_sine_for_plotting() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let opts = RootSearchOptions{
+            lower: -5.0,
+            upper: 5.0,
+            resolution: 200,
+            ..Default::default()
+        };
+        let (res, samples) = roots_dense::<_,Dual32,f32>(sine, opts);
+        assert_eq!(res.roots, root_search::<_,Dual32,f32>(sine, opts).roots);
+        assert_eq!(samples.len(), 201);
+        assert_eq!(samples[0].0, -5.0);
+        assert_eq!(samples[200].0, 5.0);
+        assert!(samples.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert!(samples.iter().all(|(x, y)| *y == x.sin()));
+        // The samples that the sign detection takes to be zero are still plotted as they are
+        let (_, snapped) = roots_dense::<_,Dual32,f32>(sine, RootSearchOptions{sign_epsilon: 0.05, ..opts});
+        assert!(snapped.iter().all(|(x, y)| *y == x.sin()));
+        assert!(snapped.iter().any(|(_, y)| *y != 0.0 && y.abs() <= 0.05));
+    }
+
+    #[test]
+    fn refine_sine_roots_to_significant_digits() {
+        use std::f32::consts::PI;
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let res = roots_refined_to::<_,Dual32,f32>(sine, 6, RootSearchOptions{
+            lower: 1.0,
+            upper: 10.0,
+            tolerance: 0.1,
+            ..Default::default()
+        });
+        assert_eq!(res.roots.len(), 3);
+        for (root, k) in res.roots.iter().zip(1..=3) {
+            let expected = k as f32 * PI;
+            assert!(((root - expected) / expected).abs() < 0.000001);
+        }
+        let rounded = roots_refined_to::<_,Dual32,f32>(sine, 6, RootSearchOptions{
+            lower: 1.0,
+            upper: 10.0,
+            tolerance: 0.1,
+            round_to_decimals: Some(3),
+            ..Default::default()
+        });
+        let expected: Vec<f32> = (1..=3).map(|k| round_to_decimals(k as f32 * PI, 3)).collect();
+        assert_eq!(rounded.roots, expected);
+        assert_ne!(rounded.roots, res.roots);
+    }
+
+    #[test]
+    fn merge_overlapping_searches_by_root_key() {
+        use std::collections::HashSet;
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let left = root_search::<_,Dual32,f32>(sine, RootSearchOptions{
+            lower: -5.0,
+            upper: 1.0,
+            ..Default::default()
+        });
+        let right = root_search::<_,Dual32,f32>(sine, RootSearchOptions{
+            lower: -1.0,
+            upper: 5.0,
+            resolution: 789,
+            ..Default::default()
+        });
+        let merged: HashSet<RootKey<f32>> = left.roots.iter().chain(&right.roots)
+            .map(|root| RootKey::new(*root, 0.0001).unwrap())
+            .collect();
+        assert_eq!(merged.len(), 3);
+        assert!(merged.contains(&RootKey::new(0.0, 0.0001).unwrap()));
+        assert!(merged.contains(&RootKey::new(std::f32::consts::PI, 0.0001).unwrap()));
+    }
+
+    #[test]
+    fn leave_unquantisable_roots_out_of_set() {
+        assert!(RootKey::new(1e16_f64, 0.0001).is_none());
+        assert!(RootKey::new(1.0_f64, 0.0).is_none());
+        assert!(RootKey::new(0.0_f64, 0.0).is_none());
+        assert!(RootKey::new(f64::NAN, 0.0001).is_none());
+        fn far<D: DualNum<f64>>(x: D) -> D {
+            x - 1e16
+        }
+        let opts = RootSearchOptions{
+            lower: 0.0,
+            upper: 2e16,
+            resolution: 3,
+            ..Default::default()
+        };
+        assert_eq!(root_search::<_,Dual64,f64>(far, opts).roots, vec![1e16]);
+        assert!(root_search_set::<_,Dual64,f64>(far, opts).is_empty());
+        fn sine<D: DualNum<f64>>(x: D) -> D {
+            x.sin()
+        }
+        let exact = RootSearchOptions{tolerance: 0.0, lower: 1.0, upper: 5.0, ..opts};
+        assert_eq!(root_search::<_,Dual64,f64>(sine, exact).roots.len(), 1);
+        assert!(root_search_set::<_,Dual64,f64>(sine, exact).is_empty());
+    }
+
+    #[test]
+    fn pick_sine_search_resolution() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let (resolution, res) = root_search_auto_resolution::<_,Dual32,f32>(sine, RootSearchOptions{
+            lower: -5.0,
+            upper: 5.0,
+            resolution: 2,
+            ..Default::default()
+        }, 1000);
+        assert_eq!(res.roots.len(), 3);
+        assert!(resolution > 2 && resolution <= 1000);
+        let full = root_search::<_,Dual32,f32>(sine, RootSearchOptions{
+            lower: -5.0,
+            upper: 5.0,
+            ..Default::default()
+        });
+        assert!(res.roots.iter().zip(&full.roots).all(|(a, b)| (a - b).abs() < 0.0001));
+    }
+
+    #[test]
+    fn track_shifted_sine_roots() {
+        fn shifted_sine<D: DualNum<f32>>(x: D, shift: D) -> D {
+            (x - shift).sin()
+        }
+        let roots = root_search_sensitivities::<_,Dual32,f32>(shifted_sine, 0.5, RootSearchOptions{
+            lower: -5.0,
+            upper: 5.0,
+            ..Default::default()
+        }).unwrap();
+        assert_eq!(roots.len(), 3);
+        for ((root, sensitivity), k) in roots.into_iter().zip(-1..=1) {
+            assert!((root - 0.5 - k as f32 * std::f32::consts::PI).abs() < 0.0001);
+            assert!((sensitivity - 1.0).abs() < 0.0001);
+        }
+    }
+
+    #[test]
+    fn find_roots_of_boxed_functions() {
+        let functions: Vec<BoxedFunction<Dual32>> = vec![
+            Box::new(|x: Dual32| x.sin()),
+            Box::new(|x: Dual32| x * x - 2.0),
+            Box::new(|x: Dual32| x.exp() - 1.0)
+        ];
+        let opts = RootSearchOptions{
+            lower: -5.0,
+            upper: 5.0,
+            ..Default::default()
+        };
+        let counts: Vec<usize> = functions.iter()
+            .map(|f| root_search_boxed::<_,f32>(f, opts).roots.len())
+            .collect();
+        assert_eq!(counts, vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn find_roots_of_closure_capturing_coefficients() {
+        // Owning the coefficients makes the closure `Clone` but not `Copy`
+        let coefficients: Vec<f32> = vec![-2.0, 0.0, 1.0];
+        let quadratic = move |x: Dual32| coefficients.iter().rev().fold(Dual32::from(0.0), |acc, c| acc * x + *c);
+        let res = root_search::<_,Dual32,f32>(quadratic.clone(), RootSearchOptions{
+            lower: -2.0,
+            upper: 2.0,
+            ..Default::default()
+        });
+        assert_eq!(res.roots.len(), 2);
+        assert!((res.roots[1] - std::f32::consts::SQRT_2).abs() < 0.0001);
+        let res = newton::<_,Dual32,f32>(quadratic, NewtonOptions{
+            guess: 1.0,
+            ..Default::default()
+        });
+        assert!((res.root.unwrap() - std::f32::consts::SQRT_2).abs() < 0.0001);
+    }
+
+    #[test]
+    fn find_only_falling_sine_roots() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let res = root_search::<_,Dual32,f32>(&sine, RootSearchOptions{
+            lower: -5.0,
+            upper: 5.0,
+            direction: Some(Crossing::Falling),
+            ..Default::default()
+        });
+        assert_eq!(res.roots, vec![-std::f32::consts::PI, std::f32::consts::PI]);
+        assert_eq!(res.crossings, vec![Crossing::Falling, Crossing::Falling]);
+        assert_eq!(res.bisections.len(), 2);
+    }
+
+    #[test]
+    fn keep_roots_outside_of_bracket() {
+        // The function is too steep around its root at pi for any guess in the bracket to converge
+        // to it, so Newton's method overshoots and settles on another multiple of pi far away
+        fn steep_sine<D: DualNum<f32>>(x: D) -> D {
+            (x.sin() * 10.0).atan()
+        }
+        let opts = RootSearchOptions{
+            lower: 2.5,
+            upper: 3.6,
+            resolution: 1,
+            ..Default::default()
+        };
+        let strict = root_search::<_,Dual32,f32>(&steep_sine, opts);
+        assert!(strict.roots.is_empty());
+        assert!(strict.outside_bracket_roots.is_empty());
+        let kept = root_search::<_,Dual32,f32>(&steep_sine, RootSearchOptions{
+            keep_outside_bracket: true,
+            ..opts
+        });
+        assert!(kept.roots.is_empty());
+        assert_eq!(kept.outside_bracket_roots.len(), 1);
+        let stray = kept.outside_bracket_roots[0];
+        assert!(!(2.5..=3.6).contains(&stray));
+        assert!(stray.sin().abs() < 0.001);
+    }
+
+    #[test]
+    fn find_sine_roots_with_swapped_bounds() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let ordered = root_search::<_,Dual32,f32>(&sine, RootSearchOptions{
+            lower: -5.0,
+            upper: 5.0,
+            ..Default::default()
+        });
+        let swapped = root_search::<_,Dual32,f32>(&sine, RootSearchOptions{
+            lower: 5.0,
+            upper: -5.0,
+            auto_order_bounds: true,
+            ..Default::default()
+        });
+        assert_eq!(ordered.roots, swapped.roots);
+    }
+
+    #[test]
+    #[should_panic]
+    fn reject_swapped_bounds_by_default() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        root_search::<_,Dual32,f32>(&sine, RootSearchOptions{
+            lower: 5.0,
+            upper: -5.0,
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    fn find_sine_target_roots() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let res = root_search::<_,Dual32,f32>(&sine, RootSearchOptions{
+            lower: 0.0,
+            upper: 3.0,
+            target: 0.5,
+            ..Default::default()
+        });
+        assert_eq!(res.roots.len(), 2);
+        assert!((res.roots[0] - std::f32::consts::FRAC_PI_6).abs() < 0.0001);
+        assert!((res.roots[1] - 5.0 * std::f32::consts::FRAC_PI_6).abs() < 0.0001);
+        let res = newton::<_,Dual32,f32>(&sine, NewtonOptions{
+            guess: 0.0,
+            target: 0.5,
+            ..Default::default()
+        });
+        assert!((res.root.unwrap() - std::f32::consts::FRAC_PI_6).abs() < 0.0001);
+    }
+
+    #[test]
+    fn find_sine_roots_near_point() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let roots = roots_near::<_,Dual32,f32>(&sine, 3.0, 4.0, RootSearchOptions::default());
+        assert_eq!(roots.len(), 3);
+        assert_eq!(roots[0], std::f32::consts::PI);
+        assert_eq!(roots[1], 0.0);
+    }
+
+    #[test]
+    fn solve_all_finds_tangent_root() {
+        fn tangent<D: DualNum<f32>>(x: D) -> D {
+            (x.clone() - 1.0).powi(2) * (x + 2.0)
+        }
+        let bracketing = RootSearchOptions::default();
+        let multistart = Newton::new().with_starts(50);
+        let bracketed = Solver::<_,Dual32,f32>::find_roots(&bracketing, &tangent, -3.0, 3.0);
+        assert_eq!(bracketed.len(), 1);
+        let roots = solve_all::<_,Dual32,f32>(&[&bracketing, &multistart], &tangent, -3.0, 3.0, 0.001);
+        assert_eq!(roots.len(), 2);
+        assert!((roots[0] + 2.0).abs() < 0.0001);
+        assert!((roots[1] - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn warm_start_parameter_sweep() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        let search = RootSearchOptions{
+            lower: 2.0,
+            upper: 4.5,
+            ..Default::default()
+        };
+        let cold_evaluations = AtomicUsize::new(0);
+        let warm_evaluations = AtomicUsize::new(0);
+        let mut warm = WarmStart::new(Newton::new(), search);
+        for i in 0..20 {
+            let shift = i as f32 * 0.05;
+            let cold = root_search::<_,Dual32,f32>(|x: Dual32| {
+                cold_evaluations.fetch_add(1, Ordering::Relaxed);
+                (x - shift).sin()
+            }, search);
+            let root = warm.solve::<_,Dual32>(|x: Dual32| {
+                warm_evaluations.fetch_add(1, Ordering::Relaxed);
+                (x - shift).sin()
+            });
+            assert_eq!(cold.roots.len(), 1);
+            assert!((root.unwrap() - cold.roots[0]).abs() < 0.0001);
+        }
+        assert!(warm_evaluations.load(Ordering::Relaxed) * 10 < cold_evaluations.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn find_sine_root_set() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let roots = root_search_set::<_,Dual32,f32>(&sine, RootSearchOptions{
+            lower: -10.0,
+            upper: 10.0,
+            patience: 2000,
+            tolerance: 0.0001,
+            resolution: 1000,
+            ..Default::default()
+        });
+        let sorted: Vec<f32> = roots.values().copied().collect();
+        assert_eq!(sorted.len(), 7);
+        assert!(sorted.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(roots.get(&0), Some(&0.0));
+        assert_eq!(roots.get(&31416), Some(&std::f32::consts::PI));
+    }
+
+    #[test]
+    fn find_sqrt_root_newton_in_domain() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        let left_domain = AtomicBool::new(false);
+        let shifted_sqrt = |x: Dual32| {
+            if x.re < 0.0 {
+                left_domain.store(true, Ordering::Relaxed);
+            }
+            x.sqrt() - 1.0
+        };
+        let res = newton_in_domain::<_,_,Dual32,f32>(&shifted_sqrt, |x| x >= 0.0, NewtonOptions{
+            guess: 9.0,
+            patience: 1000,
+            tolerance: 0.0001,
+            ..Default::default()
+        });
+        assert!(!left_domain.load(Ordering::Relaxed));
+        assert!((res.root.unwrap() - 1.0).abs() < 0.0001)
+    }
+
+    #[test]
+    fn stop_at_walls_of_search_bounds() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        let evaluated_outside = AtomicBool::new(false);
+        let arctan = |x: Dual64| {
+            if x.re.abs() > 3.0 {
+                evaluated_outside.store(true, Ordering::Relaxed);
+            }
+            x.atan()
+        };
+        // From 2 the first Newton step overshoots to about -3.5
+        let res = solve_until_converged_or_bounds::<_,Dual64,f64>(&arctan, -3.0, 3.0, NewtonOptions{
+            guess: 2.0,
+            ..Default::default()
+        });
+        assert_eq!(res.termination, TerminationReason::LeftDomain);
+        assert_eq!((res.root, res.iterations), (None, 1));
+        assert!(!evaluated_outside.load(Ordering::Relaxed));
+        let res = solve_until_converged_or_bounds::<_,Dual64,f64>(&arctan, -3.0, 3.0, NewtonOptions{
+            guess: 1.0,
+            ..Default::default()
+        });
+        assert!(res.root.unwrap().abs() < 0.0001);
+        let res = solve_until_converged_or_bounds::<_,Dual64,f64>(&arctan, -3.0, 3.0, NewtonOptions{
+            guess: 4.0,
+            ..Default::default()
+        });
+        assert_eq!(res.termination, TerminationReason::OutsideDomain);
+    }
 
-}
\ No newline at end of file
+    mod properties {
+        use super::*;
+        use proptest::prelude::*;
+
+        const LOWER: f32 = -5.0;
+        const UPPER: f32 = 5.0;
+
+        fn search_options() -> RootSearchOptions<f32> {
+            RootSearchOptions{
+                lower: LOWER,
+                upper: UPPER,
+                ..Default::default()
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn cubic_roots_are_in_bounds_and_small(r0 in -4.0f32..4.0, r1 in -4.0f32..4.0, r2 in -4.0f32..4.0) {
+                let cubic = move |x: Dual32| (x - r0) * (x - r1) * (x - r2);
+                let res = root_search::<_,Dual32,f32>(cubic, search_options());
+                for root in res.roots {
+                    prop_assert!((LOWER..=UPPER).contains(&root));
+                    prop_assert!(cubic(Dual32::from(root)).re.abs() < 0.001);
+                }
+            }
+
+            #[test]
+            fn trig_roots_are_in_bounds_and_small(a in 0.5f32..2.0, b in 0.5f32..3.0, c in -0.9f32..0.9) {
+                let wave = move |x: Dual32| (x * b).sin() * a + c;
+                let res = root_search::<_,Dual32,f32>(wave, search_options());
+                for root in res.roots {
+                    prop_assert!((LOWER..=UPPER).contains(&root));
+                    prop_assert!(wave(Dual32::from(root)).re.abs() < 0.001);
+                }
+            }
+
+            #[test]
+            fn separated_cubic_roots_are_all_found(r0 in -4.0f32..-2.0, r1 in -1.0f32..1.0, r2 in 2.0f32..4.0) {
+                let cubic = move |x: Dual32| (x - r0) * (x - r1) * (x - r2);
+                let res = root_search::<_,Dual32,f32>(cubic, search_options());
+                prop_assert_eq!(res.roots.len(), 3);
+                for (root, expected) in res.roots.iter().zip([r0, r1, r2]) {
+                    prop_assert!((root - expected).abs() < 0.001);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use std::{fmt::{self, Display}, ops::{Add, Div, Mul, Sub}};
+use num_dual::DualNumFloat;
+use crate::{from_scalar, root_search, CoerceError, Coerceable, Derivable, RootSearchOptions};
+
+/// A polynomial with real coefficients, in ascending powers of `x`, evaluated by Horner's method.
+///
+/// Horner's method takes one multiplication and one addition per coefficient, and rounds less than
+/// summing the powers of `x`. Passing `|x| polynomial.at(x)` to `newton` or `root_search`, with
+/// `N = HornerPoint<T>`, solves the polynomial without going through dual numbers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polynomial<T> where T: DualNumFloat {
+    pub coefficients: Vec<T>
+}
+
+impl <T: DualNumFloat> Polynomial<T> {
+    pub fn new(coefficients: Vec<T>) -> Self {
+        Polynomial{coefficients}
+    }
+
+    /// The value of the polynomial at `x`
+    pub fn evaluate(&self, x: T) -> T {
+        self.coefficients.iter().rev().fold(T::zero(), |acc, c| acc * x + *c)
+    }
+
+    /// The value of the polynomial and of its derivative at `x`, from a single pass of Horner's
+    /// method in which the derivative is accumulated from the partial values
+    pub fn evaluate_with_derivative(&self, x: T) -> (T, T) {
+        self.coefficients.iter().rev().fold((T::zero(), T::zero()), |(value, derivative), c| (value * x + *c, derivative * x + value))
+    }
+
+    /// The derivative of the polynomial, itself a polynomial of one degree less
+    pub fn derivative(&self) -> Polynomial<T> {
+        Polynomial{coefficients: self.coefficients.iter().enumerate().skip(1).map(|(i, c)| *c * T::from(i).unwrap()).collect()}
+    }
+
+    /// The polynomial at a `HornerPoint`, carrying its derivative through by the chain rule
+    pub fn at(&self, x: HornerPoint<T>) -> HornerPoint<T> {
+        let (value, derivative) = self.evaluate_with_derivative(x.value);
+        HornerPoint{value, derivative: derivative * x.derivative}
+    }
+}
+
+/// A value along with its derivative, which `Polynomial::at` evaluates natively, so that a
+/// polynomial can be solved through the `Derivable` interface
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HornerPoint<T> where T: DualNumFloat {
+    pub value: T,
+    pub derivative: T
+}
+
+impl <T: DualNumFloat> Derivable<T> for HornerPoint<T> {
+    fn execute_derivative(&self) -> Self {
+        HornerPoint{value: self.value, derivative: T::one()}
+    }
+    fn zeroth_derivative(&self) -> T {
+        self.value
+    }
+    fn first_derivative(&self) -> T {
+        self.derivative
+    }
+}
+
+impl <T: DualNumFloat> Coerceable<T> for HornerPoint<T> {
+    fn coerce_to(&self) -> Result<T, CoerceError> {
+        Ok(self.value)
+    }
+    fn coerce_from(value: T) -> Result<Self, CoerceError> {
+        Ok(HornerPoint{value, derivative: T::zero()})
+    }
+}
+
+impl <T: DualNumFloat> Display for HornerPoint<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} + {}ε", self.value, self.derivative)
+    }
+}
+
+impl <T: DualNumFloat> Sub for HornerPoint<T> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        HornerPoint{value: self.value - other.value, derivative: self.derivative - other.derivative}
+    }
+}
+
+impl <T: DualNumFloat> Div for HornerPoint<T> {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        HornerPoint{
+            value: self.value / other.value,
+            derivative: (self.derivative * other.value - self.value * other.derivative) / (other.value * other.value)
+        }
+    }
+}
+
+pub struct PolynomialOptions<T> where T: DualNumFloat {
+    pub search: RootSearchOptions<T>,
+    /// Count the real roots in the search range with a Sturm sequence and warn if fewer were found
+    pub verify_count: bool
+}
+
+impl <T: DualNumFloat> Default for PolynomialOptions<T> {
+    fn default() -> Self {
+        PolynomialOptions{
+            search: RootSearchOptions::default(),
+            verify_count: false
+        }
+    }
+}
+
+pub struct PolynomialResult<T> where T: DualNumFloat {
+    pub roots: Vec<T>,
+    /// The number of distinct real roots in the search range, if it was asked to be verified
+    pub expected_count: Option<usize>
+}
+
+/// Finds the real roots of the polynomial with the given coefficients, in ascending powers of `x`.
+pub fn polynomial_roots<N, T>(coefficients: &[T], opts: PolynomialOptions<T>) -> PolynomialResult<T>
+where
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Add<Output = N> + Sub<Output = N> + Mul<Output = N> + Div<Output = N> + Send + Sync,
+    T: DualNumFloat
+{
+    if coefficients.iter().any(|c| from_scalar::<N, T>(*c).is_err()) {
+        log::warn!("The coefficients cannot be represented by the dual number type");
+        return PolynomialResult{roots: Vec::new(), expected_count: None};
+    }
+    // Every coefficient was checked to be representable above, as zero always is
+    let evaluate = |x: N| coefficients.iter().rev().fold(from_scalar::<N, T>(T::zero()).unwrap(), |acc, c| acc * x.clone() + from_scalar(*c).unwrap());
+    let roots = root_search(evaluate, opts.search).roots;
+    let expected_count = if opts.verify_count {
+        let mut shifted = coefficients.to_vec();
+        if let Some(constant) = shifted.first_mut() {
+            *constant = *constant - opts.search.target;
+        }
+        let count = sturm_count(&shifted, opts.search.lower, opts.search.upper);
+        if count != roots.len() {
+            log::warn!("Found {} roots in [{}, {}] but the polynomial has {}, try increasing the resolution", roots.len(), opts.search.lower, opts.search.upper, count);
+        }
+        Some(count)
+    } else {
+        None
+    };
+    PolynomialResult{roots, expected_count}
+}
+
+/// Counts the distinct real roots of a polynomial in `(lower, upper]` using its Sturm sequence.
+///
+/// The coefficients are given in ascending powers of `x`.
+pub fn sturm_count<T>(coefficients: &[T], lower: T, upper: T) -> usize where T: DualNumFloat {
+    let sequence = sturm_sequence(coefficients);
+    sign_changes(&sequence, lower) - sign_changes(&sequence, upper)
+}
+
+fn sturm_sequence<T>(coefficients: &[T]) -> Vec<Vec<T>> where T: DualNumFloat {
+    let mut sequence = vec![trim(coefficients.to_vec())];
+    let derivative: Vec<T> = coefficients.iter().enumerate().skip(1).map(|(i, c)| *c * T::from(i).unwrap()).collect();
+    let mut next = trim(derivative);
+    while !next.is_empty() {
+        let remainder = remainder(sequence.last().unwrap(), &next);
+        sequence.push(next);
+        next = trim(remainder.into_iter().map(|c| -c).collect());
+    }
+    sequence
+}
+
+fn remainder<T>(dividend: &[T], divisor: &[T]) -> Vec<T> where T: DualNumFloat {
+    let mut remainder = dividend.to_vec();
+    let lead = *divisor.last().unwrap();
+    while remainder.len() >= divisor.len() {
+        let factor = *remainder.last().unwrap() / lead;
+        let offset = remainder.len() - divisor.len();
+        for (i, c) in divisor.iter().enumerate() {
+            remainder[offset + i] = remainder[offset + i] - factor * *c;
+        }
+        remainder.pop();
+    }
+    trim(remainder)
+}
+
+/// Drops leading coefficients that are negligible compared to the largest one
+fn trim<T>(mut coefficients: Vec<T>) -> Vec<T> where T: DualNumFloat {
+    let scale = coefficients.iter().fold(T::zero(), |max, c| max.max(c.abs()));
+    let threshold = scale * T::epsilon() * T::from(16).unwrap();
+    while coefficients.last().is_some_and(|c| c.abs() <= threshold) {
+        coefficients.pop();
+    }
+    coefficients
+}
+
+fn sign_changes<T>(sequence: &[Vec<T>], x: T) -> usize where T: DualNumFloat {
+    let values: Vec<T> = sequence.iter()
+        .map(|p| p.iter().rev().fold(T::zero(), |acc, c| acc * x + *c))
+        .filter(|value| *value != T::zero())
+        .collect();
+    values.windows(2).filter(|pair| pair[0].signum() != pair[1].signum()).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{newton, NewtonOptions};
+    use num_dual::Dual32;
+
+    #[test]
+    fn evaluate_and_differentiate_with_horner() {
+        // (x - 1)(x - 2)(x + 3) = x^3 - 7x + 6
+        let cubic = Polynomial::new(vec![6.0, -7.0, 0.0, 1.0]);
+        assert_eq!(cubic.evaluate(0.0), 6.0);
+        assert_eq!(cubic.evaluate(2.0), 0.0);
+        assert_eq!(cubic.evaluate(-1.5), 13.125);
+        assert_eq!(cubic.evaluate_with_derivative(1.0), (0.0, -4.0));
+        assert_eq!(cubic.evaluate_with_derivative(-2.0), (12.0, 5.0));
+        assert_eq!(cubic.derivative(), Polynomial::new(vec![-7.0, 0.0, 3.0]));
+        let point = cubic.at(HornerPoint{value: 3.0, derivative: 1.0});
+        assert_eq!((point.value, point.derivative), (cubic.evaluate(3.0), cubic.derivative().evaluate(3.0)));
+        let res = newton::<_,HornerPoint<f64>,f64>(|x| cubic.at(x), NewtonOptions{
+            guess: 0.5,
+            ..Default::default()
+        });
+        assert!((res.root.unwrap() - 1.0).abs() < 0.0001);
+        let roots = root_search::<_,HornerPoint<f64>,f64>(|x| cubic.at(x), RootSearchOptions{
+            lower: -5.0,
+            upper: 5.0,
+            ..Default::default()
+        }).roots;
+        assert_eq!(roots.len(), 3);
+        for (root, expected) in roots.iter().zip([-3.0, 1.0, 2.0]) {
+            assert!((root - expected).abs() < 0.0001);
+        }
+    }
+
+    #[test]
+    fn detect_missed_close_roots() {
+        // (x - 1)(x - 1.1)(x + 1)
+        let coefficients = [1.1, -1.0, -1.1, 1.0];
+        let res = polynomial_roots::<Dual32, f32>(&coefficients, PolynomialOptions{
+            search: RootSearchOptions{
+                lower: -2.0,
+                upper: 2.0,
+                resolution: 10,
+                ..Default::default()
+            },
+            verify_count: true
+        });
+        assert_eq!(res.roots.len(), 1);
+        assert_eq!(res.expected_count, Some(3));
+    }
+}
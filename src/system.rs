@@ -0,0 +1,358 @@
+use nalgebra::{Const, Dyn, U1};
+use num_dual::{Derivative, DualNumFloat, DualVec, DualSVec32, DualSVec64, DualDVec32, DualDVec64};
+use crate::TerminationReason;
+
+/// The multivariate counterpart of [`Derivable`](crate::Derivable).
+///
+/// `Derivable` is implemented by a scalar dual number and exposes the value and the derivative of
+/// a function of one variable. `DerivableVec` is implemented by a vector of dual numbers and
+/// exposes the values and the Jacobian of a function of several variables, so that a function
+/// `Fn(V) -> V` takes the place of `Fn(N) -> N`. Where `execute_derivative` seeds the single
+/// derivative of a scalar, `execute_jacobian` seeds component `i` with the `i`-th unit direction.
+pub trait DerivableVec<T> where T: DualNumFloat {
+    fn execute_jacobian(&self) -> Self;
+    fn values(&self) -> Vec<T>;
+    fn jacobian(&self) -> Vec<Vec<T>>;
+}
+
+/// The multivariate counterpart of [`Coerceable`](crate::Coerceable).
+pub trait CoerceableVec<T> where T: DualNumFloat {
+    fn coerce_to(&self) -> Vec<T>;
+    fn coerce_from(values: &[T]) -> Self;
+}
+
+macro_rules! impl_static_vec {
+    ($float:ty, $dual:ident) => {
+        impl <const D: usize> DerivableVec<$float> for [$dual<D>; D] {
+            fn execute_jacobian(&self) -> Self {
+                let mut seeded = *self;
+                for (i, x) in seeded.iter_mut().enumerate() {
+                    x.eps = Derivative::derivative_generic(Const::<D>, U1, i);
+                }
+                seeded
+            }
+            fn values(&self) -> Vec<$float> {
+                self.iter().map(|x| x.re).collect()
+            }
+            fn jacobian(&self) -> Vec<Vec<$float>> {
+                self.iter().map(|x| x.eps.unwrap_generic(Const::<D>, U1).iter().copied().collect()).collect()
+            }
+        }
+
+        impl <const D: usize> CoerceableVec<$float> for [$dual<D>; D] {
+            fn coerce_to(&self) -> Vec<$float> {
+                self.values()
+            }
+            fn coerce_from(values: &[$float]) -> Self {
+                std::array::from_fn(|i| DualVec::from_re(values[i]))
+            }
+        }
+    };
+}
+
+macro_rules! impl_dynamic_vec {
+    ($float:ty, $dual:ident) => {
+        impl DerivableVec<$float> for Vec<$dual> {
+            fn execute_jacobian(&self) -> Self {
+                let n = self.len();
+                self.iter().enumerate().map(|(i, x)| {
+                    DualVec::new(x.re, Derivative::derivative_generic(Dyn(n), U1, i))
+                }).collect()
+            }
+            fn values(&self) -> Vec<$float> {
+                self.iter().map(|x| x.re).collect()
+            }
+            fn jacobian(&self) -> Vec<Vec<$float>> {
+                let n = self.len();
+                self.iter().map(|x| x.eps.clone().unwrap_generic(Dyn(n), U1).iter().copied().collect()).collect()
+            }
+        }
+
+        impl CoerceableVec<$float> for Vec<$dual> {
+            fn coerce_to(&self) -> Vec<$float> {
+                self.values()
+            }
+            fn coerce_from(values: &[$float]) -> Self {
+                values.iter().map(|&x| DualVec::from_re(x)).collect()
+            }
+        }
+    };
+}
+
+impl_static_vec!(f32, DualSVec32);
+impl_static_vec!(f64, DualSVec64);
+impl_dynamic_vec!(f32, DualDVec32);
+impl_dynamic_vec!(f64, DualDVec64);
+
+/// A solver for the square linear systems `a x = b` that `newton_system` solves at every step.
+///
+/// `a` is the Jacobian given as a vector of rows and `b` the right hand side. Implementations
+/// should return the solution `x`, or `None` when the system is singular, and are free to consume
+/// `a` and `b` as scratch space. This is the place to wire in an optimised backend such as
+/// `nalgebra` or `ndarray` for larger systems.
+pub trait LinearSolve<T> where T: DualNumFloat {
+    fn solve(&self, a: Vec<Vec<T>>, b: Vec<T>) -> Option<Vec<T>>;
+}
+
+/// Dense LU decomposition with partial pivoting, the default `LinearSolve` backend. A system whose
+/// pivot is zero or not finite, e.g. from a Jacobian holding NaN, is treated as singular.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DenseLu;
+
+impl <T: DualNumFloat> LinearSolve<T> for DenseLu {
+    fn solve(&self, mut a: Vec<Vec<T>>, mut b: Vec<T>) -> Option<Vec<T>> {
+        let n = b.len();
+        for k in 0..n {
+            // NaN is ranked above every number, so that it is taken as the pivot and rejected
+            let pivot = (k..n).max_by(|&i, &j| {
+                a[i][k].abs().partial_cmp(&a[j][k].abs()).unwrap_or_else(|| a[i][k].is_nan().cmp(&a[j][k].is_nan()))
+            })?;
+            if a[pivot][k] == T::zero() || !a[pivot][k].is_finite() {
+                return None;
+            }
+            a.swap(k, pivot);
+            b.swap(k, pivot);
+            let (pivot_rows, rows) = a.split_at_mut(k + 1);
+            let pivot_row = &pivot_rows[k];
+            for (i, row) in rows.iter_mut().enumerate() {
+                let factor = row[k] / pivot_row[k];
+                for (entry, pivot) in row[k..].iter_mut().zip(&pivot_row[k..]) {
+                    *entry = *entry - factor * *pivot;
+                }
+                b[k + 1 + i] = b[k + 1 + i] - factor * b[k];
+            }
+        }
+        let mut x = vec![T::zero(); n];
+        for i in (0..n).rev() {
+            let sum = (i + 1..n).fold(b[i], |sum, j| sum - a[i][j] * x[j]);
+            x[i] = sum / a[i][i];
+        }
+        Some(x)
+    }
+}
+
+/// A norm measuring the size of the residuals and steps of `newton_system` against the tolerance
+pub trait Norm<T> where T: DualNumFloat {
+    fn norm(&self, x: &[T]) -> T;
+}
+
+/// The Euclidean norm, the default `Norm`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct L2;
+
+impl <T: DualNumFloat> Norm<T> for L2 {
+    fn norm(&self, x: &[T]) -> T {
+        x.iter().fold(T::zero(), |sum, x| sum + *x * *x).sqrt()
+    }
+}
+
+/// The largest absolute component
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LInfinity;
+
+impl <T: DualNumFloat> Norm<T> for LInfinity {
+    fn norm(&self, x: &[T]) -> T {
+        x.iter().fold(T::zero(), |max, x| max.max(x.abs()))
+    }
+}
+
+/// The Euclidean norm with each component scaled by its weight first, for systems whose
+/// components differ widely in scale
+#[derive(Debug, Clone)]
+pub struct Weighted<T> where T: DualNumFloat {
+    /// One weight for each component, so `norm` panics unless there are as many of them as components
+    pub weights: Vec<T>
+}
+
+impl <T: DualNumFloat> Norm<T> for Weighted<T> {
+    fn norm(&self, x: &[T]) -> T {
+        assert_eq!(self.weights.len(), x.len(), "Expected one weight for each of the components");
+        x.iter().zip(&self.weights).fold(T::zero(), |sum, (x, w)| sum + (*x * *w) * (*x * *w)).sqrt()
+    }
+}
+
+pub struct SystemOptions<T> where T: DualNumFloat {
+    pub guess: Vec<T>,
+    pub patience: i32,
+    pub tolerance: T
+}
+
+pub struct SystemResult<T> where T: DualNumFloat {
+    pub root: Option<Vec<T>>,
+    pub iterations: i32,
+    pub termination: TerminationReason
+}
+
+/// Newton's method for a square system of equations `f(x) = 0`, solving each step with `DenseLu`
+pub fn newton_system<F, V, T>(f: F, opts: SystemOptions<T>) -> SystemResult<T>
+where
+    F: Fn(V) -> V,
+    V: DerivableVec<T> + CoerceableVec<T>,
+    T: DualNumFloat
+{
+    newton_system_with(f, &DenseLu, opts)
+}
+
+/// Newton's method for a square system of equations `f(x) = 0`, solving each step with `solver`
+/// and measuring convergence with the `L2` norm.
+pub fn newton_system_with<F, V, L, T>(f: F, solver: &L, opts: SystemOptions<T>) -> SystemResult<T>
+where
+    F: Fn(V) -> V,
+    V: DerivableVec<T> + CoerceableVec<T>,
+    L: LinearSolve<T>,
+    T: DualNumFloat
+{
+    newton_system_with_norm(f, solver, &L2, opts)
+}
+
+/// Newton's method for a square system of equations `f(x) = 0`, solving each step with `solver`.
+///
+/// The solve has converged once `norm` of either the residual `f(x)` or the step falls below the tolerance.
+pub fn newton_system_with_norm<F, V, L, M, T>(f: F, solver: &L, norm: &M, opts: SystemOptions<T>) -> SystemResult<T>
+where
+    F: Fn(V) -> V,
+    V: DerivableVec<T> + CoerceableVec<T>,
+    L: LinearSolve<T>,
+    M: Norm<T>,
+    T: DualNumFloat
+{
+    let mut current = opts.guess;
+    let mut count = 0;
+    loop {
+        count += 1;
+        let z = f(V::coerce_from(&current).execute_jacobian());
+        let residual: Vec<T> = z.values().into_iter().map(|value| -value).collect();
+        let step = match solver.solve(z.jacobian(), residual) {
+            Some(step) => step,
+            None => {
+                return SystemResult{
+                    root: None,
+                    iterations: count,
+                    termination: TerminationReason::ZeroDerivative
+                };
+            }
+        };
+        let next: Vec<T> = current.iter().zip(&step).map(|(x, dx)| *x + *dx).collect();
+        if next.iter().any(|x| !x.is_finite()) {
+            return SystemResult{
+                root: None,
+                iterations: count,
+                termination: TerminationReason::Diverged
+            };
+        }
+        if norm.norm(&z.values()) < opts.tolerance || norm.norm(&step) < opts.tolerance {
+            return SystemResult{
+                root: Some(next),
+                iterations: count,
+                termination: TerminationReason::ToleranceMet
+            };
+        }
+        if count > opts.patience {
+            return SystemResult{
+                root: None,
+                iterations: count,
+                termination: TerminationReason::PatienceExhausted
+            };
+        }
+        current = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_dual::DualNum;
+
+    fn system<D: DualNum<f64> + Clone>(x: &[D]) -> [D; 2] {
+        [x[0].clone() * x[1].clone(), x[0].clone() + x[1].powi(2)]
+    }
+
+    #[test]
+    fn static_jacobian() {
+        let x = <[DualSVec64<2>; 2]>::coerce_from(&[2.0, 3.0]).execute_jacobian();
+        let y = system(&x);
+        assert_eq!(y.values(), vec![6.0, 11.0]);
+        assert_eq!(y.jacobian(), vec![vec![3.0, 2.0], vec![1.0, 6.0]]);
+    }
+
+    #[test]
+    fn solve_circle_line_intersection() {
+        let circle_line = |x: [DualSVec64<2>; 2]| [x[0].powi(2) + x[1].powi(2) - 4.0, x[1] - x[0]];
+        let res = newton_system(circle_line, SystemOptions{
+            guess: vec![1.0, 0.5],
+            patience: 100,
+            tolerance: 1e-10
+        });
+        let root = res.root.unwrap();
+        assert_eq!(res.termination, TerminationReason::ToleranceMet);
+        assert!((root[0] - std::f64::consts::SQRT_2).abs() < 1e-10);
+        assert!((root[1] - std::f64::consts::SQRT_2).abs() < 1e-10);
+    }
+
+    #[test]
+    fn solve_with_custom_linear_solver() {
+        struct Cramer;
+        impl LinearSolve<f64> for Cramer {
+            fn solve(&self, a: Vec<Vec<f64>>, b: Vec<f64>) -> Option<Vec<f64>> {
+                let det = a[0][0] * a[1][1] - a[0][1] * a[1][0];
+                if det == 0.0 {
+                    return None;
+                }
+                Some(vec![(b[0] * a[1][1] - a[0][1] * b[1]) / det, (a[0][0] * b[1] - b[0] * a[1][0]) / det])
+            }
+        }
+        let circle_line = |x: Vec<DualDVec64>| vec![
+            x[0].powi(2) + x[1].powi(2) - 4.0,
+            x[1].clone() - x[0].clone()
+        ];
+        let res = newton_system_with(circle_line, &Cramer, SystemOptions{
+            guess: vec![1.0, 0.5],
+            patience: 100,
+            tolerance: 1e-10
+        });
+        let root = res.root.unwrap();
+        assert!((root[0] - std::f64::consts::SQRT_2).abs() < 1e-10);
+        assert!((root[1] - std::f64::consts::SQRT_2).abs() < 1e-10);
+    }
+
+    #[test]
+    fn converge_badly_scaled_system_with_weighted_norm() {
+        // The second equation is scaled so far down that its residual is always within tolerance
+        let scaled = |x: [DualSVec64<2>; 2]| [x[0].powi(2) - 2.0, (x[1].powi(2) - 3.0) * 1e-9];
+        let opts = || SystemOptions{
+            guess: vec![1.0, 100.0],
+            patience: 100,
+            tolerance: 1e-6
+        };
+        let res = newton_system(scaled, opts());
+        assert!((res.root.unwrap()[1] - 3.0_f64.sqrt()).abs() > 1e-6);
+        let weighted = Weighted{weights: vec![1.0, 1e9]};
+        let res = newton_system_with_norm(scaled, &DenseLu, &weighted, opts());
+        let root = res.root.unwrap();
+        assert!((root[0] - std::f64::consts::SQRT_2).abs() < 1e-6);
+        assert!((root[1] - 3.0_f64.sqrt()).abs() < 1e-6);
+        let res = newton_system_with_norm(scaled, &DenseLu, &LInfinity, opts());
+        assert_eq!(res.termination, TerminationReason::ToleranceMet);
+    }
+
+    #[test]
+    #[should_panic]
+    fn reject_weights_of_the_wrong_length() {
+        Weighted{weights: vec![1.0, 2.0]}.norm(&[1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn treat_non_finite_pivot_as_singular() {
+        assert_eq!(DenseLu.solve(vec![vec![1.0, 0.0], vec![f64::NAN, 1.0]], vec![1.0, 1.0]), None);
+        assert_eq!(DenseLu.solve(vec![vec![f64::INFINITY, 0.0], vec![0.0, 1.0]], vec![1.0, 1.0]), None);
+        assert_eq!(DenseLu.solve(vec![vec![2.0, 0.0], vec![0.0, 4.0]], vec![1.0, 1.0]), Some(vec![0.5, 0.25]));
+    }
+
+    #[test]
+    fn dynamic_jacobian() {
+        let x = Vec::<DualDVec64>::coerce_from(&[2.0, 3.0]).execute_jacobian();
+        let y = system(&x).to_vec();
+        assert_eq!(y.values(), vec![6.0, 11.0]);
+        assert_eq!(y.jacobian(), vec![vec![3.0, 2.0], vec![1.0, 6.0]]);
+    }
+}
@@ -0,0 +1,265 @@
+use std::{fmt::Display, ops::{Div, Sub}, sync::{atomic::{AtomicUsize, Ordering}, mpsc::Sender}, thread};
+use num_dual::{Dual32, Dual64, DualNumFloat};
+use crate::{
+    even_guesses, polish_root, refine_coarse_roots, root_search_iter, try_root_search_guessed, Coerceable, Derivable, Newton,
+    RootSearchError, RootSearchOptions, RootSearchResult, SolveBrackets, Solved, WarmStart
+};
+
+/// Runs `work` for every index below `count` on `threads` scoped threads, each of which takes the
+/// next index from a shared counter until none are left, and returns the outputs in index order
+fn share_work<W, R>(count: usize, threads: usize, work: W) -> Vec<R>
+where
+    W: Fn(usize) -> R + Sync,
+    R: Send
+{
+    let next = AtomicUsize::new(0);
+    let mut outputs = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads.max(1)).map(|_| scope.spawn(|| {
+            let mut outputs = Vec::new();
+            loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                if i >= count {
+                    break;
+                }
+                outputs.push((i, work(i)));
+            }
+            outputs
+        })).collect();
+        workers.into_iter().flat_map(|worker| worker.join().unwrap()).collect::<Vec<_>>()
+    });
+    outputs.sort_by_key(|(i, _)| *i);
+    outputs.into_iter().map(|(_, output)| output).collect()
+}
+
+/// Solves the brackets on scoped threads of the standard library, with each thread taking the next
+/// unsolved bracket from a shared counter until none are left
+struct Shared(usize);
+
+impl <T: DualNumFloat> SolveBrackets<T> for Shared {
+    fn solve_brackets<W>(&self, count: usize, solve: W, _: &mut dyn FnMut(&Solved<T>) -> bool) -> Vec<Solved<T>>
+    where
+        W: Fn(usize) -> Solved<T> + Sync
+    {
+        share_work(count, self.0, solve)
+    }
+}
+
+/// Searches for roots as in `root_search`, solving the brackets on `threads` scoped threads of the
+/// standard library instead of one after another.
+///
+/// Each thread takes the next unsolved bracket from a shared counter until none are left, and the
+/// outcomes are put back into the order of the brackets, so the result is that of the sequential
+/// search however the solves were scheduled.
+pub fn root_search_threaded<F, N, T>(f: F, threads: usize, opts: RootSearchOptions<T>) -> RootSearchResult<T>
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    match try_root_search_guessed(f, |_| true, &even_guesses, opts, &Shared(threads)) {
+        Ok(res) => res,
+        Err(RootSearchError::FlatFunction) => RootSearchResult::empty(),
+        Err(error) => panic!("{}", error)
+    }
+}
+
+/// Searches for roots as `root_search_iter` does, sending each root over `sender` as soon as it
+/// has been solved, e.g. so that a user interface can show the roots found by a search running on a
+/// background thread while the rest are still being solved.
+///
+/// The search stops early once the receiver has hung up, and returns the number of roots sent.
+pub fn root_search_to_channel<F, N, T>(f: F, sender: Sender<T>, opts: RootSearchOptions<T>) -> usize
+where
+    F: Fn(N) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    root_search_iter(f, opts).map_while(|root| sender.send(root).ok()).count()
+}
+
+/// Searches for roots as in `root_search_refined`, polishing the single precision roots in double
+/// precision on `threads` scoped threads instead of one after another.
+///
+/// Only the polish evaluates `fine`, in at most `polish_steps` Newton iterations per root, so a
+/// search over a large range evaluates it far fewer times than a pure `f64` search evaluates its
+/// function. Each root is polished independently of the others, so the threads share nothing but
+/// the roots still to be polished. The roots come back in the same order as those of `root_search_refined`.
+pub fn roots_parallel_precision<F, G>(coarse: F, fine: G, threads: usize, opts: RootSearchOptions<f64>, polish_steps: i32) -> RootSearchResult<f64>
+where
+    F: Fn(Dual32) -> Dual32 + Sync + Send,
+    G: Fn(Dual64) -> Dual64 + Sync + Send
+{
+    refine_coarse_roots(coarse, &opts, |roots| {
+        share_work(roots.len(), threads, |i| polish_root(&fine, roots[i], &opts, polish_steps))
+    })
+}
+
+/// Solves `f(x, p1, p2) = target` at every cell of the grid spanned by `p1` and `p2`, e.g. for a
+/// phase diagram, returning the root of each cell with `roots[i][j]` at `(p1[i], p2[j])`.
+///
+/// The rows of the grid are shared out among scoped threads of the standard library, and each row
+/// is swept with a `WarmStart`, so that every cell starts from the root of its neighbour along the row.
+pub fn solve_batch_parallel_grid<F, N, T>(f: F, p1: &[T], p2: &[T], newton: Newton<T>, search: RootSearchOptions<T>) -> Vec<Vec<Option<T>>>
+where
+    F: Fn(N, T, T) -> N + Sync + Send,
+    N: Derivable<T> + Coerceable<T> + Display + Clone + Sub + Div,
+    T: DualNumFloat
+{
+    let threads = thread::available_parallelism().map_or(1, |threads| threads.get()).min(p1.len());
+    share_work(p1.len(), threads, |i| {
+        let mut warm = WarmStart::new(newton, search);
+        p2.iter().map(|b| warm.solve(|x: N| f(x, p1[i], *b))).collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{root_search, root_search_refined, Symmetry};
+    use num_dual::{Dual32, DualNum};
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn threaded_search_matches_sequential() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let opts = RootSearchOptions{
+            lower: -50.0,
+            upper: 50.0,
+            keep_outside_bracket: true,
+            ..Default::default()
+        };
+        let sequential = root_search::<_,Dual32,f32>(sine, opts);
+        for threads in [1, 2, 7] {
+            let threaded = root_search_threaded::<_,Dual32,f32>(sine, threads, opts);
+            assert_eq!(threaded.roots, sequential.roots);
+            assert_eq!(threaded.crossings, sequential.crossings);
+            assert_eq!(threaded.bisections, sequential.bisections);
+            assert_eq!(threaded.outside_bracket_roots, sequential.outside_bracket_roots);
+            assert_eq!(threaded.poles, sequential.poles);
+            assert_eq!(threaded.plateaus, sequential.plateaus);
+        }
+        // The poles of the tangent and the plateau where it grazes zero next to them
+        let grazing_tangent = |x: Dual32| x.tan() * 0.001 + (x - 1.0).powi(4);
+        let opts = RootSearchOptions{
+            lower: 0.0,
+            upper: 5.0,
+            plateau_tolerance: Some(0.01),
+            ..Default::default()
+        };
+        let sequential = root_search::<_,Dual32,f32>(grazing_tangent, opts);
+        assert!(!sequential.poles.is_empty());
+        assert!(!sequential.plateaus.is_empty());
+        let threaded = root_search_threaded::<_,Dual32,f32>(grazing_tangent, 3, opts);
+        assert_eq!(threaded.roots, sequential.roots);
+        assert_eq!(threaded.poles, sequential.poles);
+        assert_eq!(threaded.plateaus, sequential.plateaus);
+        // The symmetry hint and the cap on the roots are honoured as in the sequential search
+        for opts in [
+            RootSearchOptions{lower: -50.0, upper: 50.0, symmetry: Symmetry::Odd, ..Default::default()},
+            RootSearchOptions{lower: -50.0, upper: 50.0, max_total_roots: Some(4), exhaust_brackets: true, ..Default::default()}
+        ] {
+            let sequential = root_search::<_,Dual32,f32>(sine, opts);
+            let threaded = root_search_threaded::<_,Dual32,f32>(sine, 4, opts);
+            assert_eq!(threaded.roots, sequential.roots);
+            assert_eq!(threaded.crossings, sequential.crossings);
+        }
+    }
+
+    #[test]
+    fn stream_roots_over_channel() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        let opts = RootSearchOptions{
+            lower: -10.0,
+            upper: 10.0,
+            ..Default::default()
+        };
+        let (sender, receiver) = channel();
+        let solver = thread::spawn(move || root_search_to_channel::<_,Dual32,f32>(sine, sender, opts));
+        let streamed: Vec<f32> = receiver.iter().collect();
+        assert_eq!(solver.join().unwrap(), streamed.len());
+        assert_eq!(streamed, root_search::<_,Dual32,f32>(sine, opts).roots);
+        let (sender, receiver) = channel();
+        drop(receiver);
+        assert_eq!(root_search_to_channel::<_,Dual32,f32>(sine, sender, opts), 0);
+    }
+
+    #[test]
+    fn parallel_polish_matches_refined_search() {
+        fn sine<D: DualNum<f32>>(x: D) -> D {
+            x.sin()
+        }
+        fn fine_sine<D: DualNum<f64>>(x: D) -> D {
+            x.sin()
+        }
+        let opts = RootSearchOptions{
+            lower: -100.0,
+            upper: 100.0,
+            tolerance: 1e-12,
+            resolution: 10000,
+            ..Default::default()
+        };
+        let refined = root_search_refined(sine, fine_sine, opts, 10);
+        let exact = root_search::<_,Dual64,f64>(fine_sine, opts);
+        for threads in [1, 4] {
+            let parallel = roots_parallel_precision(sine, fine_sine, threads, opts, 10);
+            assert_eq!(parallel.roots, refined.roots);
+            assert_eq!(parallel.roots.len(), exact.roots.len());
+            for (root, expected) in parallel.roots.iter().zip(&exact.roots) {
+                assert!((root - expected).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn polish_with_fewer_double_precision_evaluations() {
+        let polished = AtomicUsize::new(0);
+        let direct = AtomicUsize::new(0);
+        let coarse = |x: Dual32| x.sin();
+        let fine = |x: Dual64| {
+            polished.fetch_add(1, Ordering::Relaxed);
+            x.sin()
+        };
+        let exact = |x: Dual64| {
+            direct.fetch_add(1, Ordering::Relaxed);
+            x.sin()
+        };
+        let opts = RootSearchOptions{
+            lower: -100.0,
+            upper: 100.0,
+            tolerance: 1e-12,
+            resolution: 10000,
+            ..Default::default()
+        };
+        let parallel = roots_parallel_precision(coarse, fine, 4, opts, 10);
+        let expected = root_search::<_,Dual64,f64>(exact, opts);
+        assert_eq!(parallel.roots.len(), expected.roots.len());
+        // The polish takes a handful of evaluations per root, against the whole grid and every
+        // Newton solve of the pure `f64` search
+        let (polished, direct) = (polished.into_inner(), direct.into_inner());
+        assert!(polished <= 11 * parallel.roots.len());
+        assert!(10 * polished < direct, "{} polishing against {} direct evaluations", polished, direct);
+    }
+
+    #[test]
+    fn solve_shifted_sine_over_parameter_grid() {
+        use std::f32::consts::PI;
+        let p1 = [0.0, 0.1, 0.2];
+        let p2 = [-0.3, -0.1, 0.1, 0.3];
+        let roots = solve_batch_parallel_grid::<_,Dual32,f32>(|x: Dual32, a, b| (x - a - b).sin(), &p1, &p2, Newton::new(), RootSearchOptions{
+            lower: 2.0,
+            upper: 4.5,
+            ..Default::default()
+        });
+        assert_eq!(roots.len(), p1.len());
+        for (row, a) in roots.iter().zip(p1) {
+            assert_eq!(row.len(), p2.len());
+            for (root, b) in row.iter().zip(p2) {
+                assert!((root.unwrap() - (PI + a + b)).abs() < 0.0001);
+            }
+        }
+    }
+}